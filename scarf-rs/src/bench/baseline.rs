@@ -0,0 +1,235 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// One measured metric for one application, keyed by its directory path, suitable for
+/// saving to a baseline file and diffing against a later run. `bench test` uses 1.0/0.0
+/// for pass/fail; `bench run` uses the mean runtime in seconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricEntry {
+    pub path: String,
+    pub metric: f64,
+}
+
+/// A saved set of per-app metrics from a prior run, written by `--save-baseline` and read
+/// back by `--baseline`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    pub entries: Vec<MetricEntry>,
+}
+
+impl Baseline {
+    pub fn new(entries: Vec<MetricEntry>) -> Self {
+        Self { entries }
+    }
+
+    /// Baseline files are plain (uncolored) JSON so they stay machine-readable when read
+    /// back by a later run.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write baseline to {}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline from {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse baseline at {}", path.display()))
+    }
+}
+
+/// How a metric changed relative to its baseline value.
+pub enum Classification {
+    Improved,
+    Regressed,
+    Unchanged,
+}
+
+impl Classification {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Classification::Improved => "improved",
+            Classification::Regressed => "regressed",
+            Classification::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// One row of a baseline comparison: the app, its baseline/current metric, the percent
+/// delta, and a classification against `threshold`.
+pub struct ComparisonRow {
+    pub path: String,
+    pub baseline: f64,
+    pub current: f64,
+    pub delta_pct: f64,
+    pub classification: Classification,
+}
+
+/// Compare `current` entries against a `baseline`, matching by directory path. Apps that
+/// appear in only one side are skipped (nothing to compare). `higher_is_worse` controls
+/// whether a positive delta (e.g. a slower mean runtime) or a negative delta (e.g. fewer
+/// passes) is the regression direction; `threshold` is the percent magnitude a change must
+/// exceed to count as a regression/improvement rather than noise.
+pub fn compare(
+    baseline: &Baseline,
+    current: &[MetricEntry],
+    threshold: f64,
+    higher_is_worse: bool,
+) -> Vec<ComparisonRow> {
+    let mut rows = Vec::new();
+    for entry in current {
+        let Some(base) = baseline.entries.iter().find(|b| b.path == entry.path) else {
+            continue;
+        };
+
+        let delta_pct = if base.metric == 0.0 {
+            // Percent change from a zero baseline is undefined; express the transition
+            // as an absolute percentage-point delta instead so a 0->nonzero change (e.g.
+            // a previously-failing app now passing) still clears the threshold rather
+            // than being silently reported as unchanged.
+            (entry.metric - base.metric) * 100.0
+        } else {
+            (entry.metric - base.metric) / base.metric * 100.0
+        };
+        let regression_signal = if higher_is_worse { delta_pct } else { -delta_pct };
+        let classification = if regression_signal > threshold {
+            Classification::Regressed
+        } else if regression_signal < -threshold {
+            Classification::Improved
+        } else {
+            Classification::Unchanged
+        };
+
+        rows.push(ComparisonRow {
+            path: entry.path.clone(),
+            baseline: base.metric,
+            current: entry.metric,
+            delta_pct,
+            classification,
+        });
+    }
+    rows
+}
+
+pub fn any_regressed(rows: &[ComparisonRow]) -> bool {
+    rows.iter()
+        .any(|row| matches!(row.classification, Classification::Regressed))
+}
+
+pub fn render_comparison(rows: &[ComparisonRow]) {
+    let mut table = comfy_table::Table::new();
+    table.load_preset(comfy_table::presets::UTF8_FULL_CONDENSED);
+    table.set_header(vec![
+        "Application Path",
+        "Baseline",
+        "Current",
+        "Delta",
+        "Classification",
+    ]);
+    for row in rows {
+        table.add_row(vec![
+            row.path.clone(),
+            format!("{:.3}", row.baseline),
+            format!("{:.3}", row.current),
+            format!("{:+.1}%", row.delta_pct),
+            row.classification.label().to_string(),
+        ]);
+    }
+    println!("{}", table);
+}
+
+/// Save `entries` to `--save-baseline` and/or compare them against `--baseline`, returning
+/// `true` if the comparison (when run) found a regression.
+pub fn handle(
+    entries: &[MetricEntry],
+    save_baseline: Option<&PathBuf>,
+    baseline: Option<&PathBuf>,
+    threshold: f64,
+    higher_is_worse: bool,
+) -> Result<bool> {
+    if let Some(path) = save_baseline {
+        Baseline::new(entries.to_vec()).save(path)?;
+        log::info!("Saved baseline to {}", path.display());
+    }
+
+    if let Some(path) = baseline {
+        let baseline_data = Baseline::load(path)?;
+        let rows = compare(&baseline_data, entries, threshold, higher_is_worse);
+        render_comparison(&rows);
+        return Ok(any_regressed(&rows));
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, metric: f64) -> MetricEntry {
+        MetricEntry {
+            path: path.to_string(),
+            metric,
+        }
+    }
+
+    #[test]
+    fn compare_flags_regression_beyond_threshold() {
+        let baseline = Baseline::new(vec![entry("app", 1.0)]);
+        let current = vec![entry("app", 1.2)];
+        // Runtime metric: a 20% slowdown beyond a 5% threshold is a regression.
+        let rows = compare(&baseline, &current, 5.0, true);
+        assert_eq!(rows.len(), 1);
+        assert!(matches!(rows[0].classification, Classification::Regressed));
+        assert!(any_regressed(&rows));
+    }
+
+    #[test]
+    fn compare_flags_improvement_for_opposite_direction() {
+        let baseline = Baseline::new(vec![entry("app", 1.0)]);
+        let current = vec![entry("app", 1.2)];
+        // Pass-rate metric: higher is better, so the same rise is an improvement.
+        let rows = compare(&baseline, &current, 5.0, false);
+        assert!(matches!(rows[0].classification, Classification::Improved));
+        assert!(!any_regressed(&rows));
+    }
+
+    #[test]
+    fn compare_within_threshold_is_unchanged() {
+        let baseline = Baseline::new(vec![entry("app", 1.0)]);
+        let current = vec![entry("app", 1.01)];
+        let rows = compare(&baseline, &current, 5.0, true);
+        assert!(matches!(rows[0].classification, Classification::Unchanged));
+    }
+
+    #[test]
+    fn compare_skips_paths_missing_from_baseline() {
+        let baseline = Baseline::new(vec![entry("other", 1.0)]);
+        let current = vec![entry("app", 1.0)];
+        let rows = compare(&baseline, &current, 5.0, true);
+        assert!(rows.is_empty());
+    }
+
+    #[test]
+    fn compare_from_zero_baseline_to_passing_is_improved() {
+        let baseline = Baseline::new(vec![entry("app", 0.0)]);
+        let current = vec![entry("app", 1.0)];
+        // Pass-rate metric: a previously-failing app now passing is an improvement, not
+        // "unchanged" just because the baseline was zero.
+        let rows = compare(&baseline, &current, 5.0, false);
+        assert!(matches!(rows[0].classification, Classification::Improved));
+        assert!(!any_regressed(&rows));
+    }
+
+    #[test]
+    fn compare_from_zero_baseline_to_zero_is_unchanged() {
+        let baseline = Baseline::new(vec![entry("app", 0.0)]);
+        let current = vec![entry("app", 0.0)];
+        let rows = compare(&baseline, &current, 5.0, false);
+        assert!(matches!(rows[0].classification, Classification::Unchanged));
+    }
+}
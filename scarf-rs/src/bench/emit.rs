@@ -0,0 +1,128 @@
+use serde::Serialize;
+
+use crate::utils::json_pretty;
+
+/// What an emitter is told about one application's test result, as soon as it's ready.
+pub struct EmitResult {
+    pub path: String,
+    pub label: String,
+    pub passed: bool,
+    pub stderr: String,
+}
+
+/// Hooks a `bench test` run calls as results stream in from the rayon worker channel, so
+/// progress can be rendered live instead of only after every app finishes.
+pub trait StatusEmitter {
+    fn on_start(&mut self, total: usize);
+    fn on_result(&mut self, result: &EmitResult);
+    fn on_finish(&mut self);
+}
+
+/// The original `comfy_table` dump, now rendered once every result is in.
+pub struct TerminalEmitter {
+    rows: Vec<[String; 2]>,
+}
+
+impl TerminalEmitter {
+    pub fn new() -> Self {
+        Self { rows: Vec::new() }
+    }
+}
+
+impl StatusEmitter for TerminalEmitter {
+    fn on_start(&mut self, total: usize) {
+        log::info!("Running tests for {total} application(s)...");
+    }
+
+    fn on_result(&mut self, result: &EmitResult) {
+        self.rows.push([result.path.clone(), result.label.clone()]);
+    }
+
+    fn on_finish(&mut self) {
+        let mut table = comfy_table::Table::new();
+        table.load_preset(comfy_table::presets::UTF8_FULL_CONDENSED);
+        table.set_header(vec!["Application Path", "Result"]);
+        for row in &self.rows {
+            table.add_row(row.to_vec());
+        }
+        println!("{}", table);
+    }
+}
+
+/// The shape written out by `JsonEmitter`, via the existing `json_pretty` helper.
+#[derive(Serialize)]
+struct JsonResult {
+    path: String,
+    label: String,
+    passed: bool,
+}
+
+/// Collects every result and dumps them as a single pretty-printed JSON array at the end.
+pub struct JsonEmitter {
+    results: Vec<JsonResult>,
+}
+
+impl JsonEmitter {
+    pub fn new() -> Self {
+        Self {
+            results: Vec::new(),
+        }
+    }
+}
+
+impl StatusEmitter for JsonEmitter {
+    fn on_start(&mut self, _total: usize) {}
+
+    fn on_result(&mut self, result: &EmitResult) {
+        self.results.push(JsonResult {
+            path: result.path.clone(),
+            label: result.label.clone(),
+            passed: result.passed,
+        });
+    }
+
+    fn on_finish(&mut self) {
+        println!("{}", json_pretty(&self.results));
+    }
+}
+
+/// Prints GitHub Actions workflow command annotations: a `::group::`/`::endgroup::` block
+/// wrapping the run, and one `::error::` per failing app.
+pub struct GithubEmitter;
+
+impl GithubEmitter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl StatusEmitter for GithubEmitter {
+    fn on_start(&mut self, total: usize) {
+        println!("::group::bench test ({total} application(s))");
+    }
+
+    fn on_result(&mut self, result: &EmitResult) {
+        if !result.passed {
+            let summary = result.stderr.lines().next().unwrap_or("(no stderr)");
+            println!("::error file={}::{}", result.path, summary);
+        }
+    }
+
+    fn on_finish(&mut self) {
+        println!("::endgroup::");
+    }
+}
+
+/// Select an emitter by the `--format` flag, falling back to the table emitter for any
+/// unrecognized value.
+pub fn make_emitter(format: &str) -> Box<dyn StatusEmitter> {
+    match format {
+        "json" => Box::new(JsonEmitter::new()),
+        "github" => Box::new(GithubEmitter::new()),
+        "table" => Box::new(TerminalEmitter::new()),
+        other => {
+            log::warn!("Unknown --format '{other}', falling back to 'table'.");
+            Box::new(TerminalEmitter::new())
+        }
+    }
+}
@@ -1,4 +1,7 @@
+mod baseline;
+mod emit;
 pub mod list;
+pub mod run;
 pub mod test;
 
 use anyhow::Result;
@@ -8,5 +11,6 @@ pub fn run(cmd: BenchCmd) -> Result<i32> {
     match cmd {
         BenchCmd::List(args) => list::run(args),
         BenchCmd::Test(args) => test::run(args),
+        BenchCmd::Run(args) => run::run(args),
     }
 }
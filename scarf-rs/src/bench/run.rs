@@ -0,0 +1,326 @@
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::mpsc,
+    time::Instant,
+};
+use walkdir::WalkDir;
+
+use crate::bench::baseline;
+use crate::cli::BenchRunArgs;
+
+/// Where the Linux CPU boost knob lives; writing `1`/`0` here enables/disables turbo
+/// frequency scaling across all cores.
+const CPU_BOOST_PATH: &str = "/sys/devices/system/cpu/cpufreq/boost";
+
+/// Min/max/mean/median/sample standard deviation across a set of timed iterations, in
+/// seconds.
+struct TimingStats {
+    min: f64,
+    max: f64,
+    mean: f64,
+    median: f64,
+    stddev: f64,
+}
+
+impl TimingStats {
+    fn from_samples(samples: &[f64]) -> Self {
+        let n = samples.len() as f64;
+        let min = samples.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = samples.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let mean = samples.iter().sum::<f64>() / n;
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mid = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        };
+
+        // Sample (not population) standard deviation; falls back to 0 for a single sample.
+        let variance = samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        let stddev = variance.sqrt();
+
+        Self {
+            min,
+            max,
+            mean,
+            median,
+            stddev,
+        }
+    }
+}
+
+/// Outcome of benchmarking one application.
+struct BenchResult {
+    dir: PathBuf,
+    target: &'static str,
+    stats: Option<TimingStats>,
+}
+
+/// Discover all the directories that contain the makefiles in the benchmark folder.
+fn find_app_dirs(base_dir: &PathBuf) -> Result<Vec<PathBuf>> {
+    let mut rows: Vec<PathBuf> = Vec::new();
+    for entry in WalkDir::new(base_dir) {
+        let entry = entry?;
+        if entry.file_type().is_file() && entry.file_name() == "Makefile" {
+            let leaf = entry
+                .path()
+                .parent()
+                .context("Makefile had no parent directory")
+                .unwrap()
+                .to_path_buf();
+            rows.push(leaf);
+        }
+    }
+    Ok(rows)
+}
+
+/// Prefer a `bench` target when the app's Makefile has one, falling back to `run`.
+fn choose_target(path: &Path) -> Result<&'static str> {
+    let makefile = fs::read_to_string(path.join("Makefile"))
+        .with_context(|| format!("Failed to read Makefile in {}", path.display()))?;
+    if makefile.lines().any(|line| line.starts_with("bench:")) {
+        Ok("bench")
+    } else {
+        Ok("run")
+    }
+}
+
+/// Run one `make <target>` iteration in `path`, returning whether it succeeded and how
+/// long it took.
+fn time_iteration(path: &Path, target: &str) -> Result<(bool, f64)> {
+    let start = Instant::now();
+    let status = Command::new("make")
+        .arg(target)
+        .current_dir(path)
+        .output()
+        .with_context(|| format!("Failed to execute 'make {target}' in {}", path.display()))?
+        .status;
+    Ok((status.success(), start.elapsed().as_secs_f64()))
+}
+
+/// Warm up, then time `samples` measured iterations of an app's `bench`/`run` target.
+fn run_benchmark(path: &PathBuf, warmup: u32, samples: u32) -> Result<BenchResult> {
+    if !path.join("Makefile").exists() {
+        return Err(anyhow::anyhow!(
+            "No Makefile found in the provided directory: {}",
+            path.display()
+        ));
+    }
+
+    let target = choose_target(path)?;
+    log::info!(
+        "Benchmarking {} with `make {}` ({} warmup, {} measured)",
+        path.display(),
+        target,
+        warmup,
+        samples
+    );
+
+    for i in 0..warmup {
+        log::debug!("Warmup iteration {}/{} for {}", i + 1, warmup, path.display());
+        let (ok, _) = time_iteration(path, target)?;
+        if !ok {
+            log::warn!("Warmup iteration failed for {}", path.display());
+            return Ok(BenchResult {
+                dir: path.clone(),
+                target,
+                stats: None,
+            });
+        }
+    }
+
+    let mut durations = Vec::with_capacity(samples as usize);
+    for i in 0..samples {
+        let (ok, elapsed) = time_iteration(path, target)?;
+        if !ok {
+            log::warn!("Measured iteration {}/{} failed for {}", i + 1, samples, path.display());
+            return Ok(BenchResult {
+                dir: path.clone(),
+                target,
+                stats: None,
+            });
+        }
+        log::debug!(
+            "Measured iteration {}/{} for {} took {:.3}s",
+            i + 1,
+            samples,
+            path.display(),
+            elapsed
+        );
+        durations.push(elapsed);
+    }
+
+    Ok(BenchResult {
+        dir: path.clone(),
+        target,
+        stats: Some(TimingStats::from_samples(&durations)),
+    })
+}
+
+/// Enable the Linux CPU boost knob, returning its prior value so it can be restored.
+/// Returns `None` (and logs a warning) if the knob couldn't be read or written.
+fn enable_cpu_boost() -> Option<String> {
+    let prior = fs::read_to_string(CPU_BOOST_PATH)
+        .inspect_err(|e| log::warn!("Could not read {CPU_BOOST_PATH}: {e}"))
+        .ok()?;
+    match fs::write(CPU_BOOST_PATH, "1") {
+        Ok(()) => Some(prior.trim().to_string()),
+        Err(e) => {
+            log::warn!("Could not write {CPU_BOOST_PATH}: {e}");
+            None
+        }
+    }
+}
+
+fn restore_cpu_boost(prior: Option<String>) {
+    if let Some(value) = prior {
+        if let Err(e) = fs::write(CPU_BOOST_PATH, value) {
+            log::warn!("Could not restore {CPU_BOOST_PATH}: {e}");
+        }
+    }
+}
+
+/// The run subcommand that times each benchmark application's `bench`/`run` target.
+pub fn run(args: BenchRunArgs) -> Result<i32> {
+    log::info!("Benchmarking applications...");
+
+    let bench_root = std::fs::canonicalize(PathBuf::from(args.root.as_str()))
+        .context(format!(
+            "Failed to canonicalize the benchmark root path: {}",
+            args.root
+        ))
+        .unwrap();
+    assert!(
+        bench_root.exists(),
+        "The benchmark folder {} does not exist?",
+        bench_root.display()
+    );
+    log::info!("Benchmark directory: {}", bench_root.display());
+
+    let base = match &args.layer {
+        Some(layer) => bench_root.join(layer),
+        None => bench_root.clone(),
+    };
+    log::info!("Base directory: {}", base.display());
+
+    let app_dirs = find_app_dirs(&base).expect("Failed to find application directories");
+
+    let prior_boost = args.cpu_boost.then(enable_cpu_boost).flatten();
+
+    let (tx, rx) = mpsc::channel::<(PathBuf, anyhow::Result<BenchResult>)>();
+    app_dirs.par_iter().for_each_with(tx, |tx, dir| {
+        let result = run_benchmark(dir, args.warmup, args.samples);
+        let _ = tx.send((dir.to_path_buf(), result));
+    });
+
+    if args.cpu_boost {
+        restore_cpu_boost(prior_boost);
+    }
+
+    const NO_STATS: [&str; 5] = ["-", "-", "-", "-", "-"];
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut entries: Vec<baseline::MetricEntry> = Vec::new();
+    for (dir, res) in rx.iter() {
+        let path = dir.to_string_lossy().into_owned();
+        let (target, result, stats) = match &res {
+            Ok(res) => match &res.stats {
+                Some(stats) => {
+                    entries.push(baseline::MetricEntry {
+                        path: path.clone(),
+                        metric: stats.mean,
+                    });
+                    (
+                        res.target.to_string(),
+                        "Success".to_string(),
+                        vec![
+                            format!("{:.3}s", stats.min),
+                            format!("{:.3}s", stats.max),
+                            format!("{:.3}s", stats.mean),
+                            format!("{:.3}s", stats.median),
+                            format!("{:.3}s", stats.stddev),
+                        ],
+                    )
+                }
+                None => (
+                    res.target.to_string(),
+                    "Failure".to_string(),
+                    NO_STATS.iter().map(|s| s.to_string()).collect(),
+                ),
+            },
+            Err(e) => {
+                log::error!("Benchmark run in {} encountered an error: {}", dir.display(), e);
+                (
+                    "-".to_string(),
+                    "Error".to_string(),
+                    NO_STATS.iter().map(|s| s.to_string()).collect(),
+                )
+            }
+        };
+
+        let mut row = vec![path, target, result];
+        row.extend(stats);
+        rows.push(row);
+    }
+
+    let header = [
+        "Application Path",
+        "Target",
+        "Result",
+        "Min",
+        "Max",
+        "Mean",
+        "Median",
+        "StdDev",
+    ];
+    let mut table = comfy_table::Table::new();
+    table.load_preset(comfy_table::presets::UTF8_FULL_CONDENSED);
+    table.set_header(header);
+    for row in rows {
+        table.add_row(row);
+    }
+    println!("{}", table);
+
+    // Mean runtime is the metric here; a higher value (slower) beyond --threshold is a
+    // regression.
+    let regressed = baseline::handle(
+        &entries,
+        args.save_baseline.as_ref(),
+        args.baseline.as_ref(),
+        args.threshold,
+        true,
+    )?;
+    Ok(if regressed { 1 } else { 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_from_single_sample() {
+        let stats = TimingStats::from_samples(&[2.0]);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 2.0);
+        assert_eq!(stats.mean, 2.0);
+        assert_eq!(stats.median, 2.0);
+        assert_eq!(stats.stddev, 0.0);
+    }
+
+    #[test]
+    fn stats_from_samples_computes_min_max_mean_median_stddev() {
+        let stats = TimingStats::from_samples(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 4.0);
+        assert_eq!(stats.mean, 2.5);
+        assert_eq!(stats.median, 2.5);
+        // Sample (Bessel-corrected, n-1=3) stddev of [1,2,3,4]: sqrt(5/3).
+        assert!((stats.stddev - (5.0f64 / 3.0).sqrt()).abs() < 1e-9);
+    }
+}
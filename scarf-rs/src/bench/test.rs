@@ -1,31 +1,97 @@
 use anyhow::{Context, Result};
-use clap::Args;
 use rayon::prelude::*;
-use std::{path::PathBuf, process::Command, sync::mpsc};
+use std::{
+    collections::VecDeque,
+    fs,
+    io::Read,
+    os::unix::process::{CommandExt, ExitStatusExt},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
 use walkdir::WalkDir;
 
-#[derive(Args, Debug)]
-pub struct BenchTestArgs {
-    #[arg(long, help = "Path to the root of the scarf benchmark.")]
-    pub root: String,
+use crate::bench::baseline;
+use crate::bench::emit::{self, EmitResult};
+use crate::cli::BenchTestArgs;
+
+/// How `make [-n] test` finished for one application.
+enum TestOutcome {
+    /// Exited cleanly with status 0.
+    Success,
+    /// Exited with a non-zero status code.
+    ExitCode(i32),
+    /// Terminated by a signal rather than exiting normally (e.g. an OOM kill).
+    Signaled(i32),
+    /// Killed after exceeding the configured `--timeout`.
+    Timeout,
+}
+
+impl TestOutcome {
+    fn is_success(&self) -> bool {
+        matches!(self, TestOutcome::Success)
+    }
+
+    /// Short label for the results table.
+    fn label(&self) -> &'static str {
+        match self {
+            TestOutcome::Success => "Success",
+            TestOutcome::ExitCode(_) => "Failure",
+            TestOutcome::Signaled(_) => "Signaled",
+            TestOutcome::Timeout => "Timeout",
+        }
+    }
 
-    #[arg(long, help = "Application layer to test.")]
-    pub layer: Option<String>,
+    /// Fuller description for the log, so a failure is reproducible from the log alone.
+    fn describe(&self) -> String {
+        match self {
+            TestOutcome::Success => "Success".to_string(),
+            TestOutcome::ExitCode(code) => format!("Failure (exit code {code})"),
+            TestOutcome::Signaled(signal) => format!("Signaled (terminated by signal {signal})"),
+            TestOutcome::Timeout => "Timeout (exceeded --timeout limit)".to_string(),
+        }
+    }
+}
 
-    #[arg(
-        long = "dry-run",
-        action = clap::ArgAction::SetTrue,
-        help = "Use dry run instead of full run."
-    )]
-    pub dry_run: bool,
+/// Result of comparing captured output against an application's golden
+/// `expected.stdout`/`expected.stderr` files, when `--check-output` (or `--bless`) is set.
+enum OutputCheck {
+    /// `--check-output` wasn't passed, or the app ships no golden files.
+    Skipped,
+    /// Captured output matched the golden files.
+    Match,
+    /// Captured output didn't match; holds a line diff against the golden files.
+    Mismatch(String),
+    /// `--bless` rewrote the golden files from the captured output.
+    Blessed,
 }
 
 /// Create a container to hold command run result
 struct RunResult {
     dir: PathBuf,
-    ok: bool,
+    outcome: TestOutcome,
     stdout: String,
     stderr: String,
+    output_check: OutputCheck,
+}
+
+impl RunResult {
+    /// A run is only a pass if the process exited cleanly *and* its output matched any
+    /// golden files it shipped.
+    fn passed(&self) -> bool {
+        self.outcome.is_success() && !matches!(self.output_check, OutputCheck::Mismatch(_))
+    }
+
+    /// Short label for the results table.
+    fn label(&self) -> String {
+        if self.outcome.is_success() && matches!(self.output_check, OutputCheck::Mismatch(_)) {
+            "Output Mismatch".to_string()
+        } else {
+            self.outcome.label().to_string()
+        }
+    }
 }
 
 /// Discover all the directories that contain the makefiles in the
@@ -50,8 +116,24 @@ fn find_app_dirs(base_dir: &PathBuf) -> Result<Vec<PathBuf>> {
     Ok(rows)
 }
 
+/// How long to sleep between polls of a running `make test` while waiting for it to
+/// finish or for its `--timeout` to elapse.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How much of each stream's head/tail to keep once a `make test` invocation's captured
+/// output crosses this budget; the middle is replaced with an omission marker, so one
+/// chatty or hung application can't blow up memory or the log.
+const HEAD_BYTES: usize = 16 * 1024;
+const TAIL_BYTES: usize = 16 * 1024;
+
 /// Run the make -n test command on the makefile in the provided directory
-fn run_makefile(path: &PathBuf, dry_run: bool) -> Result<RunResult> {
+fn run_makefile(
+    path: &PathBuf,
+    dry_run: bool,
+    check_output: bool,
+    bless: bool,
+    timeout: Option<Duration>,
+) -> Result<RunResult> {
     // Check to see if there is a makefile in the provided directory.
     if !path.join("Makefile").exists() {
         return Err(anyhow::anyhow!(
@@ -60,27 +142,227 @@ fn run_makefile(path: &PathBuf, dry_run: bool) -> Result<RunResult> {
         ));
     }
 
-    let mut cmd: Command = Command::new("make");
-
+    let mut make_args: Vec<&str> = Vec::new();
     if dry_run {
-        cmd.arg("-n");
+        make_args.push("-n");
     }
+    make_args.push("test");
 
-    cmd.arg("test");
+    log::info!(
+        "Running `make {}` in {}",
+        make_args.join(" "),
+        path.display()
+    );
 
-    let output = cmd
+    let mut child = Command::new("make")
+        .args(&make_args)
         .current_dir(path)
-        .output()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Run in its own process group so a timeout can kill the whole tree, not just
+        // the `make` process we spawned directly.
+        .process_group(0)
+        .spawn()
         .with_context(|| format!("Failed to execute 'make [-n] test' in {}", path.display()))?;
 
+    let stdout_pipe = child.stdout.take().expect("stdout was piped");
+    let stderr_pipe = child.stderr.take().expect("stderr was piped");
+    let app = path.display().to_string();
+
+    let stdout_handle = thread::spawn({
+        let app = app.clone();
+        move || capture_stream(stdout_pipe, "stdout", &app)
+    });
+    let stderr_handle = thread::spawn(move || capture_stream(stderr_pipe, "stderr", &app));
+
+    let outcome = wait_with_timeout(&mut child, timeout)?;
+
+    let stdout = stdout_handle
+        .join()
+        .unwrap_or_else(|_| "<stdout reader thread panicked>".to_string());
+    let stderr = stderr_handle
+        .join()
+        .unwrap_or_else(|_| "<stderr reader thread panicked>".to_string());
+
+    let output_check = if bless {
+        bless_golden(path, &stdout, &stderr)?
+    } else if check_output {
+        check_golden(path, &stdout, &stderr)?
+    } else {
+        OutputCheck::Skipped
+    };
+
     Ok(RunResult {
         dir: path.to_path_buf(),
-        ok: output.status.success(),
-        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
-        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        outcome,
+        stdout,
+        stderr,
+        output_check,
     })
 }
 
+/// Wait for `child` to finish, polling so we can notice a `timeout` elapsing and kill its
+/// process group rather than blocking forever on a hung `make test`.
+fn wait_with_timeout(child: &mut std::process::Child, timeout: Option<Duration>) -> Result<TestOutcome> {
+    let deadline = timeout.map(|t| Instant::now() + t);
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(match status.code() {
+                Some(0) => TestOutcome::Success,
+                Some(code) => TestOutcome::ExitCode(code),
+                None => TestOutcome::Signaled(status.signal().unwrap_or(-1)),
+            });
+        }
+
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            kill_process_group(child.id());
+            // Reap the now-dying child so it doesn't linger as a zombie.
+            let _ = child.wait();
+            return Ok(TestOutcome::Timeout);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Kill an entire process group by PGID, escalating from SIGTERM to SIGKILL.
+fn kill_process_group(pgid: u32) {
+    let _ = Command::new("kill")
+        .arg("-TERM")
+        .arg(format!("-{pgid}"))
+        .status();
+    thread::sleep(Duration::from_millis(500));
+    let _ = Command::new("kill")
+        .arg("-KILL")
+        .arg(format!("-{pgid}"))
+        .status();
+}
+
+/// Read `reader` to EOF, logging each chunk as it arrives so output shows up in the log
+/// while the app is still running rather than only after it exits. Keeps the first and
+/// last `HEAD_BYTES`/`TAIL_BYTES` of the stream in memory; anything in between is dropped
+/// and replaced with an omission marker, so a chatty or hung app can't exhaust memory.
+fn capture_stream<R: Read>(mut reader: R, stream_name: &str, app: &str) -> String {
+    let mut buf = [0u8; 8192];
+    let mut head: Vec<u8> = Vec::new();
+    let mut tail: VecDeque<u8> = VecDeque::new();
+    let mut truncating = false;
+    // Bytes actually dropped from `tail`, not inferred from the head/tail budget — the
+    // chunk that first crosses the budget can overshoot `TAIL_BYTES` by more than a
+    // single read, so only a running count of real pop_fronts is accurate.
+    let mut dropped = 0usize;
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(_) => break,
+        };
+        let chunk = &buf[..n];
+        log::debug!(
+            "[{app}] {stream_name}: {}",
+            String::from_utf8_lossy(chunk).trim_end()
+        );
+
+        if !truncating {
+            head.extend_from_slice(chunk);
+            if head.len() > HEAD_BYTES + TAIL_BYTES {
+                truncating = true;
+                tail.extend(head.split_off(HEAD_BYTES));
+                while tail.len() > TAIL_BYTES {
+                    tail.pop_front();
+                    dropped += 1;
+                }
+            }
+        } else {
+            tail.extend(chunk.iter().copied());
+            while tail.len() > TAIL_BYTES {
+                tail.pop_front();
+                dropped += 1;
+            }
+        }
+    }
+
+    if !truncating {
+        String::from_utf8_lossy(&head).into_owned()
+    } else {
+        let head_str = String::from_utf8_lossy(&head).into_owned();
+        let tail_bytes: Vec<u8> = tail.into_iter().collect();
+        let tail_str = String::from_utf8_lossy(&tail_bytes).into_owned();
+        format!("{head_str}\n<{dropped} bytes omitted>\n{tail_str}")
+    }
+}
+
+/// Compare captured stdout/stderr against `expected.stdout`/`expected.stderr` next to the
+/// Makefile, if either exists. Both sides are normalized the same way before comparing, so
+/// trailing whitespace and the app's own directory path don't cause spurious failures.
+fn check_golden(path: &Path, stdout: &str, stderr: &str) -> Result<OutputCheck> {
+    let stdout_golden = path.join("expected.stdout");
+    let stderr_golden = path.join("expected.stderr");
+    if !stdout_golden.exists() && !stderr_golden.exists() {
+        return Ok(OutputCheck::Skipped);
+    }
+
+    let mut diff = String::new();
+    for (golden, actual, name) in [
+        (&stdout_golden, stdout, "expected.stdout"),
+        (&stderr_golden, stderr, "expected.stderr"),
+    ] {
+        if !golden.exists() {
+            continue;
+        }
+        let expected = normalize_output(&fs::read_to_string(golden)?, path);
+        let actual = normalize_output(actual, path);
+        if expected != actual {
+            diff.push_str(&format!("--- {name}\n"));
+            diff.push_str(&line_diff(&expected, &actual));
+        }
+    }
+
+    if diff.is_empty() {
+        Ok(OutputCheck::Match)
+    } else {
+        Ok(OutputCheck::Mismatch(diff))
+    }
+}
+
+/// Rewrite `expected.stdout`/`expected.stderr` from the actual (normalized) output.
+fn bless_golden(path: &Path, stdout: &str, stderr: &str) -> Result<OutputCheck> {
+    fs::write(path.join("expected.stdout"), normalize_output(stdout, path))?;
+    fs::write(path.join("expected.stderr"), normalize_output(stderr, path))?;
+    Ok(OutputCheck::Blessed)
+}
+
+/// Trim trailing whitespace per line and substitute the app's own directory (the closest
+/// thing to a tmpdir here) with a stable `[[TMPDIR]]` placeholder, so golden files don't
+/// bake in a path that changes between checkouts.
+fn normalize_output(raw: &str, tmp_dir: &Path) -> String {
+    let placeholder = tmp_dir.to_string_lossy().into_owned();
+    raw.lines()
+        .map(|line| line.trim_end().replace(placeholder.as_str(), "[[TMPDIR]]"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A minimal unified-style line diff: no line-matching/context, just `-`/`+` pairs for
+/// every line position where expected and actual disagree.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let len = expected_lines.len().max(actual_lines.len());
+
+    let mut diff = String::new();
+    for i in 0..len {
+        let e = expected_lines.get(i).copied().unwrap_or("");
+        let a = actual_lines.get(i).copied().unwrap_or("");
+        if e != a {
+            diff.push_str(&format!("-{e}\n+{a}\n"));
+        }
+    }
+    diff
+}
+
 /// The test subcommand that runs make test on all the applications to ensure they work as expected
 pub fn run(args: BenchTestArgs) -> Result<i32> {
     log::info!("Running tests to ensure functionality of benchmark applications...");
@@ -115,71 +397,123 @@ pub fn run(args: BenchTestArgs) -> Result<i32> {
     //                                 │                  │
     //                                Tx type     Rx Result (Result of makefile run)
 
-    // Each item in the apps_dir will be sent to the following closure such that the closure gets
-    // a tx (a transmitter of its own to the common channel) and the reference to the dir to do its work
-    app_dirs.par_iter().for_each_with(tx, |tx, dir| {
-        // Each worker does its job (i.e., run the makefile and return the result as RunResult)
-        log::info!("Running makefile test in directory: {}", dir.display());
-        let result = run_makefile(dir, args.dry_run);
-        let status = result
-            .as_ref()
-            .map_or("Error", |r| if r.ok { "Success" } else { "Failure" });
-        log::info!(
-            "Completed makefile test in directory: {}. Status: {}",
-            dir.display(),
-            status
-        );
-        // Now, clone into an owned directory (using to_path_buf) that each of the worker is
-        // using and send that back to the receiver along with the ownership of the result.
-        let _ = tx.send((dir.to_path_buf(), result));
-    });
-
-    let mut results: Vec<[String; 2]> = Vec::new();
-    //              Only iterate as many times as we have directories
-    //                                       │
-    //                                       ▼
-    //                             |```````````````````|
-    // for (dir, res) in rx.iter().take(app_dirs.len()) {
-    for (dir, res) in rx.iter() {
-        match res {
-            Ok(res) if res.ok => {
-                results.push([dir.to_string_lossy().into_owned(), "Success".to_string()]);
-                log::info!(
-                    "Makefile test in {} succeeded. Output:\n{}",
-                    res.dir.display(),
-                    res.stdout
-                );
-            }
-            Ok(res) => {
-                results.push([dir.to_string_lossy().into_owned(), "Failure".to_string()]);
-                log::warn!(
-                    "Makefile test in {} failed. Stderr:\n{}",
-                    res.dir.display(),
-                    res.stderr
+    // Dispatch onto its own scoped thread so the channel can be drained (and results
+    // emitted) concurrently with the workers still running, instead of only after every
+    // worker finishes; `thread::scope` lets it borrow `args`/`app_dirs` without an Arc
+    // since the scope can't return until the spawned thread has joined.
+    let entries = thread::scope(|scope| {
+        scope.spawn(|| {
+            // Each item in the apps_dir will be sent to the following closure such that the closure gets
+            // a tx (a transmitter of its own to the common channel) and the reference to the dir to do its work
+            app_dirs.par_iter().for_each_with(tx, |tx, dir| {
+                // Each worker does its job (i.e., run the makefile and return the result as RunResult)
+                log::info!("Running makefile test in directory: {}", dir.display());
+                let result = run_makefile(
+                    dir,
+                    args.dry_run,
+                    args.check_output,
+                    args.bless,
+                    args.timeout.map(Duration::from_secs),
                 );
-            }
-            Err(e) => {
-                results.push([dir.to_string_lossy().into_owned(), "Error".to_string()]);
-                log::error!(
-                    "Makefile test in {} encountered an error: {}",
+                let status = result
+                    .as_ref()
+                    .map_or("Error".to_string(), |r| r.outcome.describe());
+                log::info!(
+                    "Completed makefile test in directory: {}. Status: {}",
                     dir.display(),
-                    e
+                    status
                 );
+                // Now, clone into an owned directory (using to_path_buf) that each of the worker is
+                // using and send that back to the receiver along with the ownership of the result.
+                let _ = tx.send((dir.to_path_buf(), result));
+            });
+        });
+
+        let mut emitter = emit::make_emitter(&args.format);
+        emitter.on_start(app_dirs.len());
+
+        let mut entries: Vec<baseline::MetricEntry> = Vec::new();
+        // Results are emitted as they arrive, while the dispatch thread above is still
+        // sending more, rather than only after every worker has finished.
+        for (dir, res) in rx.iter() {
+            let path = dir.to_string_lossy().into_owned();
+            match &res {
+                Ok(r) if r.passed() => {
+                    entries.push(baseline::MetricEntry {
+                        path: path.clone(),
+                        metric: 1.0,
+                    });
+                    emitter.on_result(&EmitResult {
+                        path,
+                        label: r.label(),
+                        passed: true,
+                        stderr: r.stderr.clone(),
+                    });
+                    log::info!(
+                        "Makefile test in {} succeeded. Output:\n{}",
+                        r.dir.display(),
+                        r.stdout
+                    );
+                }
+                Ok(r) => {
+                    if let OutputCheck::Mismatch(diff) = &r.output_check {
+                        log::warn!(
+                            "Makefile test in {} produced unexpected output:\n{}",
+                            r.dir.display(),
+                            diff
+                        );
+                    }
+                    entries.push(baseline::MetricEntry {
+                        path: path.clone(),
+                        metric: 0.0,
+                    });
+                    emitter.on_result(&EmitResult {
+                        path,
+                        label: r.label(),
+                        passed: false,
+                        stderr: r.stderr.clone(),
+                    });
+                    log::warn!(
+                        "Makefile test in {} {}. Stderr:\n{}",
+                        r.dir.display(),
+                        r.outcome.describe(),
+                        r.stderr
+                    );
+                }
+                Err(e) => {
+                    entries.push(baseline::MetricEntry {
+                        path: path.clone(),
+                        metric: 0.0,
+                    });
+                    emitter.on_result(&EmitResult {
+                        path,
+                        label: "Error".to_string(),
+                        passed: false,
+                        stderr: e.to_string(),
+                    });
+                    log::error!(
+                        "Makefile test in {} encountered an error: {}",
+                        dir.display(),
+                        e
+                    );
+                }
             }
         }
-    }
 
-    let header: [String; 2] = ["Application Path".to_string(), "Result".to_string()];
-    let mut table: comfy_table::Table = comfy_table::Table::new();
+        emitter.on_finish();
+        entries
+    });
 
-    // Tabulate the final results
-    table.load_preset(comfy_table::presets::UTF8_FULL_CONDENSED);
-    table.set_header(header);
-    for rows in results {
-        table.add_row(rows.to_vec());
-    }
-    println!("{}", table);
-    Ok(0)
+    // Pass/fail (1.0/0.0) is the metric here; a higher value is always better, so a drop
+    // below --threshold is what counts as a regression.
+    let regressed = baseline::handle(
+        &entries,
+        args.save_baseline.as_ref(),
+        args.baseline.as_ref(),
+        args.threshold,
+        false,
+    )?;
+    Ok(if regressed { 1 } else { 0 })
 }
 
 // =====[ Unit Tests ]=====
@@ -220,15 +554,28 @@ mod tests {
         _touch_makefile(&app_dir).expect("Failed to create Makefile in app directory");
 
         // Run the makefile in dry-run mode
-        let result = run_makefile(&app_dir, false).expect("Failed to run the makefile");
+        let result =
+            run_makefile(&app_dir, true, false, false, None).expect("Failed to run the makefile");
 
         // Validate the RunResult captures the makefile directory correctly
         assert_eq!(result.dir, app_dir);
 
-        // The output must be okay
-        assert!(result.ok);
+        // The outcome must be a success
+        assert!(result.outcome.is_success());
 
         // The stdout should contain the echo command
         assert!(result.stdout.contains("echo Hello World"));
     }
+
+    #[test]
+    fn line_diff_reports_mismatched_lines_only() {
+        let diff = line_diff("a\nb\nc", "a\nX\nc");
+        assert_eq!(diff, "-b\n+X\n");
+    }
+
+    #[test]
+    fn line_diff_handles_length_mismatch() {
+        let diff = line_diff("a\nb", "a");
+        assert_eq!(diff, "-b\n+\n");
+    }
 }
@@ -1,4 +1,7 @@
-use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{ArgAction, Args, Parser, Subcommand};
+use serde::Serialize;
 
 #[derive(Parser, Debug)]
 #[command(name = "scarf", version, about = "ScarfBench CLI")]
@@ -25,6 +28,11 @@ pub enum Commands {
         about = "A series of subcommands to run on the benchmark applications."
     )]
     Bench(BenchCmd),
+    #[command(
+        subcommand,
+        about = "A series of subcommands to evaluate agents against the benchmark."
+    )]
+    Eval(EvalCmd),
 }
 
 /// Again, enums work here because we choose one of the subcommands for bench.
@@ -34,6 +42,10 @@ pub enum BenchCmd {
     List(BenchListArgs),
     #[command(about = "Run regression tests (with `make test`) on the benchmark application(s).")]
     Test(BenchTestArgs),
+    #[command(
+        about = "Time the benchmark application(s) (with `make bench`/`make run`) and report statistics."
+    )]
+    Run(BenchRunArgs),
 }
 
 #[derive(Args, Debug)]
@@ -59,4 +71,254 @@ pub struct BenchTestArgs {
         help = "Use dry run instead of full run."
     )]
     pub dry_run: bool,
+
+    #[arg(
+        long = "check-output",
+        action = ArgAction::SetTrue,
+        help = "Compare captured stdout/stderr against expected.stdout/expected.stderr next to the Makefile; a mismatch fails the test even if the exit code was 0."
+    )]
+    pub check_output: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Rewrite expected.stdout/expected.stderr from the actual output instead of comparing against them."
+    )]
+    pub bless: bool,
+
+    #[arg(
+        long = "save-baseline",
+        value_name = "PATH",
+        help = "Write this run's per-app results to a baseline JSON file for later comparison."
+    )]
+    pub save_baseline: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Compare this run's per-app results against a baseline JSON file saved with --save-baseline."
+    )]
+    pub baseline: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value_t = 5.0,
+        help = "Percent change beyond which an app is classified as regressed/improved rather than unchanged."
+    )]
+    pub threshold: f64,
+
+    #[arg(
+        long,
+        default_value = "table",
+        help = "Output format for test results: table, json, or github (GitHub Actions workflow annotations)."
+    )]
+    pub format: String,
+
+    #[arg(
+        long = "timeout",
+        value_name = "SECS",
+        help = "Kill a single `make test` invocation and mark it as timed out if it runs longer than this many seconds."
+    )]
+    pub timeout: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+pub struct BenchRunArgs {
+    #[arg(long, help = "Path to the root of the scarf repository.")]
+    pub root: String,
+
+    #[arg(long, help = "Application layer to benchmark.")]
+    pub layer: Option<String>,
+
+    #[arg(
+        long,
+        default_value_t = 3,
+        help = "Number of discarded warmup iterations run before timing begins."
+    )]
+    pub warmup: u32,
+
+    #[arg(
+        long,
+        default_value_t = 10,
+        help = "Number of measured iterations to time and compute statistics over."
+    )]
+    pub samples: u32,
+
+    #[arg(
+        long = "cpu-boost",
+        action = ArgAction::SetTrue,
+        help = "Enable CPU frequency boost for the duration of the run (Linux only), restoring the prior setting afterward."
+    )]
+    pub cpu_boost: bool,
+
+    #[arg(
+        long = "save-baseline",
+        value_name = "PATH",
+        help = "Write this run's per-app mean runtimes to a baseline JSON file for later comparison."
+    )]
+    pub save_baseline: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Compare this run's per-app mean runtimes against a baseline JSON file saved with --save-baseline."
+    )]
+    pub baseline: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value_t = 5.0,
+        help = "Percent slowdown beyond which an app is classified as regressed (a speedup beyond this classifies it as improved)."
+    )]
+    pub threshold: f64,
+}
+
+/// Again, enums work here because we choose one of the subcommands for eval.
+#[derive(Subcommand, Debug)]
+pub enum EvalCmd {
+    #[command(about = "Run an agent against benchmark applications and score the results.")]
+    Run(EvalRunArgs),
+    #[command(about = "Follow the live progress of an in-flight or completed eval run.")]
+    Watch(EvalWatchArgs),
+    #[command(about = "Compare two eval runs and report per-app pass@k deltas and regressions.")]
+    Compare(EvalCompareArgs),
+}
+
+#[derive(Args, Debug, Serialize)]
+pub struct EvalRunArgs {
+    #[arg(
+        long = "benchmark-dir",
+        help = "Path (directory) to the benchmark.",
+        value_name = "DIR"
+    )]
+    pub benchmark_dir: PathBuf,
+
+    #[arg(
+        long = "agent-dir",
+        help = "Path (directory) to agent implementation harness.",
+        value_name = "DIR"
+    )]
+    pub agent_dir: PathBuf,
+
+    #[arg(
+        long,
+        value_name = "LAYER",
+        action = ArgAction::Append,
+        help = "Application layer to run agent on.",
+    )]
+    pub layer: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "APP",
+        action = ArgAction::Append,
+        help = "Application to run the agent on. If layer is specified, this app must lie within that layer."
+    )]
+    pub app: Vec<String>,
+
+    #[arg(
+        long = "from-framework",
+        help = "The source framework for conversion.",
+        value_name = "FRAMEWORK"
+    )]
+    pub from_framework: String,
+
+    #[arg(
+        long = "to-framework",
+        help = "The target framework for conversion.",
+        value_name = "FRAMEWORK"
+    )]
+    pub to_framework: String,
+
+    #[arg(
+        short,
+        long = "pass-at-k",
+        default_value_t = 1,
+        help = "Value of K to run for generating an Pass@K value.",
+        value_name = "K"
+    )]
+    pub pass_at_k: u32,
+
+    #[arg(
+        long,
+        help = "Output directory where the agent runs and evaluation output are stored."
+    )]
+    pub eval_out: PathBuf,
+
+    #[arg(
+        short,
+        long = "jobs",
+        default_value_t = 1,
+        help = "Number of parallel jobs to run."
+    )]
+    pub jobs: u32,
+
+    #[arg(
+        long = "timeout",
+        value_name = "SECS",
+        help = "Kill an agent and mark its instance as timed out if it runs longer than this many seconds."
+    )]
+    pub timeout: Option<u64>,
+
+    #[arg(
+        long = "sandbox",
+        action = ArgAction::SetTrue,
+        help = "Run each agent in a fresh mount+PID+network namespace with only its own input/output/validation dirs and a read-only agent dir bind-mounted in."
+    )]
+    pub sandbox: bool,
+
+    #[arg(
+        long = "allow-network",
+        action = ArgAction::SetTrue,
+        help = "Keep network access inside the sandbox (--sandbox drops it by default)."
+    )]
+    pub allow_network: bool,
+
+    #[arg(
+        long = "sandbox-memory-mb",
+        default_value_t = 2048,
+        value_name = "MB",
+        help = "Memory rlimit (in MB) applied to a sandboxed agent."
+    )]
+    pub sandbox_memory_mb: u64,
+
+    #[arg(
+        long = "prepare-only",
+        action = ArgAction::SetTrue,
+        help = "Prepare the evaluation harness to run agents. Think of this as a dry run before actually deploying the agents."
+    )]
+    pub prepare_only: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct EvalWatchArgs {
+    #[arg(
+        long = "eval-out",
+        help = "Output directory of the eval run to follow (the same --eval-out it was started with)."
+    )]
+    pub eval_out: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct EvalCompareArgs {
+    #[arg(
+        long,
+        help = "Output directory (--eval-out) of the baseline run, e.g. the main branch's run.",
+        value_name = "DIR"
+    )]
+    pub baseline: PathBuf,
+
+    #[arg(
+        long,
+        help = "Output directory (--eval-out) of the candidate run, e.g. a PR's run.",
+        value_name = "DIR"
+    )]
+    pub candidate: PathBuf,
+
+    #[arg(
+        long = "fail-on-regression",
+        action = ArgAction::SetTrue,
+        help = "Exit non-zero if any instance that passed in the baseline now fails in the candidate."
+    )]
+    pub fail_on_regression: bool,
 }
@@ -0,0 +1,144 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use comfy_table::Table;
+use walkdir::WalkDir;
+
+use crate::cli::EvalCompareArgs;
+use crate::eval::score::{ScoreReport, VALIDATION_PASSED};
+use crate::eval::types::RunMetaData;
+
+/// A baseline/candidate run's per-app pass@k, read back from its `pass_at_k.json` report.
+struct AppEntry {
+    n: usize,
+    c: usize,
+    pass_at_k: f64,
+}
+
+/// Compare a baseline and a candidate eval run: per-app pass@k deltas (joined by the
+/// `layer__app__from__to` portion of each app's id, so two different agents' runs still
+/// line up) plus a hard check for instances that passed in the baseline but now fail in
+/// the candidate, suitable for gating CI on a PR's run against main's.
+pub fn run(args: EvalCompareArgs) -> anyhow::Result<i32> {
+    let baseline_apps = load_app_scores(&args.baseline)?;
+    let candidate_apps = load_app_scores(&args.candidate)?;
+
+    let mut app_keys: Vec<&String> = baseline_apps.keys().chain(candidate_apps.keys()).collect();
+    app_keys.sort();
+    app_keys.dedup();
+
+    let mut table = Table::new();
+    table.set_header(vec!["App", "Baseline Pass@K", "Candidate Pass@K", "Delta"]);
+    for app_key in &app_keys {
+        let baseline = baseline_apps.get(*app_key);
+        let candidate = candidate_apps.get(*app_key);
+        let delta = candidate.map(|a| a.pass_at_k).unwrap_or(0.0)
+            - baseline.map(|a| a.pass_at_k).unwrap_or(0.0);
+        table.add_row(vec![
+            (*app_key).clone(),
+            format_score(baseline),
+            format_score(candidate),
+            format!("{:+.1}%", delta * 100.0),
+        ]);
+    }
+    println!("{}", table);
+
+    let baseline_samples = load_sample_statuses(&args.baseline)?;
+    let candidate_samples = load_sample_statuses(&args.candidate)?;
+
+    let mut regressions: Vec<&String> = baseline_samples
+        .iter()
+        .filter(|(_, status)| status.as_str() == VALIDATION_PASSED)
+        .filter_map(|(sample_key, _)| {
+            let still_passes = candidate_samples
+                .get(sample_key)
+                .is_some_and(|status| status == VALIDATION_PASSED);
+            (!still_passes).then_some(sample_key)
+        })
+        .collect();
+    regressions.sort();
+
+    if regressions.is_empty() {
+        log::info!("No regressions: every baseline pass held in the candidate run.");
+    } else {
+        log::warn!(
+            "{} instance(s) passed in the baseline but now fail in the candidate: {}",
+            regressions.len(),
+            regressions
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    if args.fail_on_regression && !regressions.is_empty() {
+        return Ok(1);
+    }
+    Ok(0)
+}
+
+fn format_score(entry: Option<&AppEntry>) -> String {
+    match entry {
+        Some(e) => format!("{:.4} ({}/{})", e.pass_at_k, e.c, e.n),
+        None => "-".to_string(),
+    }
+}
+
+/// Load the per-app pass@k scores an `eval run` wrote to `<eval_out>/pass_at_k.json`,
+/// keyed by the `layer__app__from__to` portion of each app's id.
+fn load_app_scores(eval_out: &Path) -> anyhow::Result<HashMap<String, AppEntry>> {
+    let report_path = eval_out.join("pass_at_k.json");
+    let contents = fs::read_to_string(&report_path)
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {}", report_path.display(), e))?;
+    let report: ScoreReport = serde_json::from_str(&contents)?;
+
+    Ok(report
+        .apps
+        .into_iter()
+        .map(|app| {
+            (
+                strip_agent(&app.eval_id),
+                AppEntry {
+                    n: app.n,
+                    c: app.c,
+                    pass_at_k: app.pass_at_k,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Walk every instance's `metadata.json` under an eval-out root, keyed by the
+/// `layer__app__from__to__sampleN` portion of its `eval_id` so the exact same sample can
+/// be matched across two runs regardless of which agent produced them.
+fn load_sample_statuses(eval_out: &Path) -> anyhow::Result<HashMap<String, String>> {
+    let mut statuses = HashMap::new();
+
+    for entry in WalkDir::new(eval_out)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() == "metadata.json")
+    {
+        let contents = fs::read_to_string(entry.path())?;
+        let Ok(metadata) = serde_json::from_str::<RunMetaData>(&contents) else {
+            continue;
+        };
+
+        let sample = metadata.eval_id().rsplit("__").next().unwrap_or("sample0");
+        let sample_key = format!(
+            "{}__{}__{}__{}__{sample}",
+            metadata.layer(),
+            metadata.app(),
+            metadata.source_framework(),
+            metadata.target_framework()
+        );
+        statuses.insert(sample_key, metadata.status().to_string());
+    }
+
+    Ok(statuses)
+}
+
+/// Strip the leading `{agent}__` from a full app/eval id, leaving `layer__app__from__to`.
+fn strip_agent(id: &str) -> String {
+    id.splitn(2, "__").nth(1).unwrap_or(id).to_string()
+}
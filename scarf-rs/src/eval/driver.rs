@@ -1,62 +1,229 @@
 use std::{
     fs::{self, File},
     io::Write,
-    path::{Path, PathBuf},
+    os::unix::process::{CommandExt, ExitStatusExt},
+    panic,
+    path::Path,
     process::Command,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
 };
 
-use crate::eval::types::{EvalLayout, RunMetaData};
-
-/// The main helper to dispatch calls to the user defined agent
-pub fn dispatch_agent(agent_dir: &Path, eval_layout: &EvalLayout) -> anyhow::Result<()> {
-    for (eval_key, eval_group) in eval_layout {
-        // If the current dir eval_root/{agent_name}__{layer}__.../ doesn't contain the current agent dir thjen we skip that
-        if !agent_dir
-            .file_name()
-            .and_then(|f| f.to_str())
-            .is_some_and(|a| eval_key.agent().eq(a))
-        {
-            continue;
+use rayon::prelude::*;
+
+use crate::eval::events::{EventLog, EventType};
+use crate::eval::sandbox::{self, SandboxOptions};
+use crate::eval::types::{EvalInstance, EvalLayout, RunMetaData};
+
+/// How a single agent run against one eval instance finished.
+enum RunOutcome {
+    /// Exited cleanly with status 0.
+    Success,
+    /// Exited with a non-zero status code.
+    ExitCode(i32),
+    /// Killed after exceeding the configured `--timeout`.
+    Timeout,
+    /// Terminated by a signal rather than exiting normally.
+    Signal(i32),
+}
+
+impl RunOutcome {
+    fn is_success(&self) -> bool {
+        matches!(self, RunOutcome::Success)
+    }
+
+    fn status_label(&self) -> String {
+        match self {
+            RunOutcome::Success => "AGENT EXECUTION COMPLETE".to_string(),
+            RunOutcome::ExitCode(code) => format!("AGENT EXECUTION FAILED (exit code {code})"),
+            RunOutcome::Timeout => "AGENT EXECUTION TIMEOUT".to_string(),
+            RunOutcome::Signal(signal) => format!("AGENT TERMINATED BY SIGNAL {signal}"),
         }
-        // TODO: The following loop ought to be parallelized...
-        for eval_instance in eval_group {
-            // Read the current eval metadata
-            let mut run_metadata: RunMetaData =
-                fs::read_to_string(eval_instance.root().join("metadata.json"))
-                    .map_err(anyhow::Error::from)
-                    .and_then(|metadata_file| {
-                        serde_json::from_str::<RunMetaData>(&metadata_file)
-                            .map_err(anyhow::Error::from)
-                    })?;
-
-            let result = Command::new("bash")
-                .arg("-lc")
-                .arg("./run.sh")
-                .current_dir(agent_dir)
-                .env("SCARF_WORK_DIR", eval_instance.output())
-                .env("SCARF_SOURCE_FRAMEWORK", run_metadata.source_framework())
-                .env("SCARF_TARGET_FRAMEWORK", run_metadata.target_framework())
-                .stderr(File::create(eval_instance.validation().join("agent.err"))?.try_clone()?)
-                .stdout(File::create(eval_instance.validation().join("agent.out"))?.try_clone()?)
-                .output()?;
-
-            if result.status.success() {
-                log::info!("Agent exectuion complete");
-                run_metadata.set_status(String::from("AGENT EXECUTION COMPLETE"));
-                update_eval_metadata(eval_instance.root(), &run_metadata)?;
-            } else {
-                run_metadata.set_status(String::from("AGENT EXECUTION FAILED"));
-                update_eval_metadata(eval_instance.root(), &run_metadata)?;
+    }
+}
+
+/// How long to sleep between polls of a running agent while waiting for it to finish or
+/// for its timeout to elapse.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Tally of how agent dispatch went across every matching eval instance.
+pub struct DispatchSummary {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// The main helper to dispatch calls to the user defined agent.
+///
+/// Runs up to `jobs` instances concurrently: a bounded rayon thread pool acts as the
+/// jobserver, handing out one of `jobs` permits to each worker as it picks up the next
+/// eval instance. Each instance already has its own `output()`/`validation()` dirs, so
+/// workers never step on each other, and a panic in one worker is caught so it can't
+/// take down the rest of the run. `timeout` bounds how long any single agent may run
+/// before its process group is killed and the instance is marked as timed out.
+pub fn dispatch_agent(
+    agent_dir: &Path,
+    jobs: usize,
+    timeout: Option<Duration>,
+    sandbox: Option<SandboxOptions>,
+    eval_layout: &EvalLayout,
+    events: &EventLog,
+) -> anyhow::Result<DispatchSummary> {
+    let agent_name = agent_dir.file_name().and_then(|f| f.to_str());
+
+    // Only the instances prepared for this agent are ours to run.
+    let instances: Vec<&EvalInstance> = eval_layout
+        .iter()
+        .filter(|(eval_key, _)| agent_name.is_some_and(|a| eval_key.agent() == a))
+        .flat_map(|(_, eval_group)| eval_group.iter())
+        .collect();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.max(1))
+        .build()?;
+
+    let (tx, rx) = mpsc::channel::<(String, anyhow::Result<bool>)>();
+
+    pool.install(|| {
+        instances.par_iter().for_each_with(tx, |tx, eval_instance| {
+            let eval_id = eval_instance.eval_id().to_string();
+            events.emit(&eval_id, EventType::AgentStarted, serde_json::json!({}));
+            let outcome = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                run_one(agent_dir, eval_instance, timeout, sandbox)
+            }))
+            .unwrap_or_else(|panic| {
+                Err(anyhow::anyhow!(
+                    "agent run for {} panicked: {}",
+                    eval_id,
+                    panic_message(&panic)
+                ))
+            });
+            events.emit(
+                &eval_id,
+                EventType::AgentFinished,
+                serde_json::json!({
+                    "succeeded": outcome.as_ref().map(|ok| *ok).unwrap_or(false),
+                    "error": outcome.as_ref().err().map(|e| e.to_string()),
+                }),
+            );
+            let _ = tx.send((eval_id, outcome));
+        });
+    });
+
+    let mut summary = DispatchSummary {
+        succeeded: Vec::new(),
+        failed: Vec::new(),
+    };
+    for (eval_id, outcome) in rx {
+        match outcome {
+            Ok(true) => {
+                log::info!("Agent execution complete for {}", eval_id);
+                summary.succeeded.push(eval_id);
+            }
+            Ok(false) => {
+                log::warn!("Agent execution failed for {}", eval_id);
+                summary.failed.push(eval_id);
+            }
+            Err(e) => {
+                log::error!("Agent execution for {} errored: {}", eval_id, e);
+                summary.failed.push(eval_id);
             }
         }
     }
-    Ok(())
+    Ok(summary)
+}
+
+/// Run a single agent invocation against one eval instance, recording the outcome in its
+/// `metadata.json`. Returns whether the run succeeded.
+fn run_one(
+    agent_dir: &Path,
+    eval_instance: &EvalInstance,
+    timeout: Option<Duration>,
+    sandbox: Option<SandboxOptions>,
+) -> anyhow::Result<bool> {
+    let mut run_metadata: RunMetaData =
+        fs::read_to_string(eval_instance.root().join("metadata.json"))
+            .map_err(anyhow::Error::from)
+            .and_then(|metadata_file| {
+                serde_json::from_str::<RunMetaData>(&metadata_file).map_err(anyhow::Error::from)
+            })?;
+
+    let mut command = match &sandbox {
+        Some(opts) => sandbox::build_sandboxed_command(agent_dir, eval_instance, opts)?,
+        None => {
+            let mut command = Command::new("bash");
+            command.arg("-lc").arg("./run.sh");
+            command
+        }
+    };
+
+    let mut child = command
+        .current_dir(agent_dir)
+        .env("SCARF_WORK_DIR", eval_instance.output())
+        .env("SCARF_SOURCE_FRAMEWORK", run_metadata.source_framework())
+        .env("SCARF_TARGET_FRAMEWORK", run_metadata.target_framework())
+        .stderr(File::create(eval_instance.validation().join("agent.err"))?)
+        .stdout(File::create(eval_instance.validation().join("agent.out"))?)
+        // Run in its own process group so a timeout can kill the whole tree, not just
+        // the process we spawned directly.
+        .process_group(0)
+        .spawn()?;
+
+    let outcome = wait_with_timeout(&mut child, timeout)?;
+
+    let success = outcome.is_success();
+    run_metadata.set_status(outcome.status_label());
+    if sandbox.is_some() {
+        if let Some(usage) = sandbox::read_resource_usage(eval_instance) {
+            run_metadata.set_resource_usage(usage);
+        }
+    }
+    update_eval_metadata(eval_instance.root(), &run_metadata)?;
+    Ok(success)
+}
+
+/// Wait for `child` to finish, polling so we can notice a `timeout` elapsing and kill its
+/// process group rather than blocking forever on a hung agent.
+fn wait_with_timeout(
+    child: &mut std::process::Child,
+    timeout: Option<Duration>,
+) -> anyhow::Result<RunOutcome> {
+    let deadline = timeout.map(|t| Instant::now() + t);
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(match status.code() {
+                Some(0) => RunOutcome::Success,
+                Some(code) => RunOutcome::ExitCode(code),
+                None => RunOutcome::Signal(status.signal().unwrap_or(-1)),
+            });
+        }
+
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            kill_process_group(child.id());
+            // Reap the now-dying child so it doesn't linger as a zombie.
+            let _ = child.wait();
+            return Ok(RunOutcome::Timeout);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
 }
 
-fn update_eval_metadata(
-    eval_instance_dir: PathBuf,
-    run_metadata: &RunMetaData,
-) -> anyhow::Result<()> {
+/// Kill an entire process group by PGID, escalating from SIGTERM to SIGKILL.
+fn kill_process_group(pgid: u32) {
+    let _ = Command::new("kill")
+        .arg("-TERM")
+        .arg(format!("-{pgid}"))
+        .status();
+    thread::sleep(Duration::from_millis(500));
+    let _ = Command::new("kill")
+        .arg("-KILL")
+        .arg(format!("-{pgid}"))
+        .status();
+}
+
+fn update_eval_metadata(eval_instance_dir: &Path, run_metadata: &RunMetaData) -> anyhow::Result<()> {
     match File::create(eval_instance_dir.join("metadata.json")) {
         Ok(mut f) => f.write_all(serde_json::to_string_pretty(run_metadata)?.as_bytes())?,
         Err(e) => {
@@ -65,3 +232,13 @@ fn update_eval_metadata(
     };
     Ok(())
 }
+
+fn panic_message(panic: &Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = panic.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = panic.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
@@ -0,0 +1,79 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The kind of progress event recorded to `events.jsonl` as an eval run proceeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    Prepared,
+    AgentStarted,
+    AgentFinished,
+    Validated,
+    RunComplete,
+}
+
+/// One line of `events.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub ts: u64,
+    pub eval_id: String,
+    pub event_type: EventType,
+    #[serde(default)]
+    pub payload: Value,
+}
+
+/// An append-only JSONL event stream that `prepare_harness` and `dispatch_agent` write
+/// progress to, so `scarf eval watch` has something to follow for long runs.
+pub struct EventLog {
+    file: Mutex<File>,
+}
+
+impl EventLog {
+    /// Open (creating if necessary) `<eval_out>/events.jsonl` for appending.
+    pub fn open(eval_out: &Path) -> anyhow::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(eval_out.join("events.jsonl"))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Append one event to the stream. Failures to log are surfaced but never abort the
+    /// caller's actual work.
+    pub fn emit(&self, eval_id: &str, event_type: EventType, payload: impl Serialize) {
+        let event = Event {
+            ts: now_unix(),
+            eval_id: eval_id.to_string(),
+            event_type,
+            payload: serde_json::to_value(payload).unwrap_or(Value::Null),
+        };
+        if let Err(e) = self.write_line(&event) {
+            log::warn!("Failed to record {:?} event for {}: {}", event_type, eval_id, e);
+        }
+    }
+
+    fn write_line(&self, event: &Event) -> anyhow::Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap();
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
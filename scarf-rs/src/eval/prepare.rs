@@ -8,56 +8,44 @@ use std::{
 use serde::Serialize;
 use walkdir::WalkDir;
 
-use crate::{eval::run::EvalRunArgs, utils};
+use crate::{
+    cli::EvalRunArgs,
+    eval::events::{EventLog, EventType},
+    eval::types::{EvalInstance, EvalInstanceKey, EvalLayout, RunMetaData},
+    utils,
+};
 
 /*
  * Some helper types
  */
-/// Here we maintain the outer layout to handle the runs
-#[derive(Serialize)]
-struct RunLayout {
-    root: PathBuf,
-    evals: HashMap<String, EvalLayout>,
-}
-
-/// This holds the eval datastructure
+/// A compact, serializable view of a prepared harness, used only for logging.
 #[derive(Serialize)]
-struct EvalLayout {
+struct RunLayoutSummary {
     root: PathBuf,
-    input: PathBuf,
-    output: PathBuf,
-    validation: PathBuf,
-}
-
-/// This is to hold the run metadata for saving in the evals folder later
-#[derive(Serialize)]
-struct RunMetaData {
-    eval_id: String,
-    agent: String,
-    layer: String,
-    app: String,
-    from_framework: String,
-    to_framework: String,
-    status: String,
+    instances: Vec<String>,
 }
 
 /// The public facing prepare harness that sets up the evaluation environment
-pub fn prepare_harness(args: &EvalRunArgs) -> anyhow::Result<()> {
-    let eval_out_dir = &args.eval_out;
-    let run_layout = RunLayout {
-        root: eval_out_dir.to_path_buf(),
-        evals: initialize_evals(args)?,
+pub fn prepare_harness(args: &EvalRunArgs, events: &EventLog) -> anyhow::Result<EvalLayout> {
+    let eval_layout = initialize_evals(args, events)?;
+    let summary = RunLayoutSummary {
+        root: args.eval_out.to_path_buf(),
+        instances: eval_layout
+            .values()
+            .flatten()
+            .map(|instance| instance.eval_id().to_string())
+            .collect(),
     };
     log::info!(
         "Evaluation harness prepared\n{}",
-        utils::json_pretty(&run_layout)
+        utils::json_pretty(&summary)
     );
-    Ok(())
+    Ok(eval_layout)
 }
 
 /// Populate the evals data structure
-fn initialize_evals(args: &EvalRunArgs) -> anyhow::Result<HashMap<String, EvalLayout>> {
-    let mut evals: HashMap<String, EvalLayout> = HashMap::new();
+fn initialize_evals(args: &EvalRunArgs, events: &EventLog) -> anyhow::Result<EvalLayout> {
+    let mut evals: EvalLayout = HashMap::new();
 
     // We'll assume for now that the agent name is the directory name where the agent is (I can change this later if needed)
     let agent_name = format!("{}", args.agent_dir.file_name().unwrap().to_string_lossy());
@@ -112,147 +100,169 @@ fn initialize_evals(args: &EvalRunArgs) -> anyhow::Result<HashMap<String, EvalLa
             app_path.display()
         );
 
-        let eval_instance_key = app_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .and_then(|app| {
-                app_path
-                    .parent()
-                    .and_then(|p| p.file_name())
-                    .and_then(|layer| layer.to_str())
-                    .map(|layer| {
-                        format!(
-                            "{}__{}__{}__{}__{}",
-                            agent_name, layer, app, args.from_framework, args.to_framework
-                        )
-                    })
-            })
-            .unwrap();
+        let Some((layer, app)) = app_path.file_name().and_then(|n| n.to_str()).and_then(|app| {
+            app_path
+                .parent()
+                .and_then(|p| p.file_name())
+                .and_then(|layer| layer.to_str())
+                .map(|layer| (layer.to_string(), app.to_string()))
+        }) else {
+            anyhow::bail!(
+                "Failed to determine layer/app for application path: {}",
+                app_path.display()
+            );
+        };
 
-        // Create a directory in the --eval-out directory
-        let eval_instance_dir = args.eval_out.join(&eval_instance_key);
+        let key = EvalInstanceKey::new(
+            agent_name.clone(),
+            layer.clone(),
+            app.clone(),
+            args.from_framework.clone(),
+            args.to_framework.clone(),
+        );
 
-        // Create the outer eval directory
-        match create_dir_all(&eval_instance_dir) {
-            Ok(_) => {
-                log::debug!(
-                    "Created eval instance directory: {}",
-                    eval_instance_dir.display()
-                );
-            }
-            Err(e) => {
-                anyhow::bail!(
-                    "Failed to create eval instance directory {}: {}",
-                    eval_instance_dir.display(),
-                    e
-                );
-            }
+        // Prepare `pass_at_k` independent samples, each with its own copy of the app, so
+        // the agent can be run K times to later compute the pass@k estimator. No
+        // per-sample seed is passed to the agent.
+        for sample in 0..args.pass_at_k {
+            let instance = prepare_sample(args, app_path, &key, sample)?;
+            events.emit(instance.eval_id(), EventType::Prepared, serde_json::json!({}));
+            evals.entry(key.clone()).or_default().push(instance);
         }
-        match create_eval_metadata(&eval_instance_dir, &eval_instance_key) {
-            Ok(_) => {
-                log::debug!(
-                    "Created eval metadata file in: {}",
-                    eval_instance_dir.display()
-                );
-            }
-            Err(e) => {
-                anyhow::bail!(
-                    "Failed to create eval metadata file in {}: {}",
-                    eval_instance_dir.display(),
-                    e
-                );
-            }
+    }
+    Ok(evals)
+}
+
+/// Prepare a single sample directory (input/output/validation) for one (layer, app,
+/// from→to) tuple, seeding it from `app_path`.
+fn prepare_sample(
+    args: &EvalRunArgs,
+    app_path: &Path,
+    key: &EvalInstanceKey,
+    sample: u32,
+) -> anyhow::Result<EvalInstance> {
+    let eval_id = format!(
+        "{}__{}__{}__{}__{}__sample{}",
+        key.agent(),
+        key.layer(),
+        key.app(),
+        key.from_framework(),
+        key.to_framework(),
+        sample
+    );
+
+    // Create a directory in the --eval-out directory
+    let eval_instance_dir = args.eval_out.join(&eval_id);
+
+    // Create the outer eval directory
+    match create_dir_all(&eval_instance_dir) {
+        Ok(_) => {
+            log::debug!(
+                "Created eval instance directory: {}",
+                eval_instance_dir.display()
+            );
+        }
+        Err(e) => {
+            anyhow::bail!(
+                "Failed to create eval instance directory {}: {}",
+                eval_instance_dir.display(),
+                e
+            );
         }
+    }
 
-        // Create the input, output, and validation directories
-        let eval_input_dir: PathBuf = eval_instance_dir.join("input");
-        match create_dir_all(&eval_input_dir) {
-            Ok(_) => {
-                log::debug!(
-                    "Created input directory: {} and seeded it with the source framework",
-                    eval_instance_dir.join("input").display()
-                );
-            }
-            Err(e) => {
-                anyhow::bail!(
-                    "Failed to create input directory {}: {}",
-                    eval_instance_dir.join("input").display(),
-                    e
-                );
-            }
+    let metadata = RunMetaData::new(
+        eval_id.clone(),
+        key.agent(),
+        key.layer(),
+        key.app(),
+        key.from_framework(),
+        key.to_framework(),
+        "PREPARED",
+    );
+    match write_eval_metadata(&eval_instance_dir, &metadata) {
+        Ok(_) => {
+            log::debug!(
+                "Created eval metadata file in: {}",
+                eval_instance_dir.display()
+            );
+        }
+        Err(e) => {
+            anyhow::bail!(
+                "Failed to create eval metadata file in {}: {}",
+                eval_instance_dir.display(),
+                e
+            );
         }
-        // Copy the app files into the input directory
-        copy_app_dir(app_path, &args.from_framework, &eval_input_dir)?;
+    }
 
-        let eval_output_dir: PathBuf = eval_instance_dir.join("output");
-        match create_dir_all(eval_instance_dir.join("output")) {
-            Ok(_) => {
-                log::debug!(
-                    "Created output directory: {} and seeded it with the source framework",
-                    eval_instance_dir.join("output").display()
-                );
-            }
-            Err(e) => {
-                anyhow::bail!(
-                    "Failed to create output directory {}: {}",
-                    eval_instance_dir.join("output").display(),
-                    e
-                );
-            }
+    // Create the input, output, and validation directories
+    let eval_input_dir: PathBuf = eval_instance_dir.join("input");
+    match create_dir_all(&eval_input_dir) {
+        Ok(_) => {
+            log::debug!(
+                "Created input directory: {} and seeded it with the source framework",
+                eval_input_dir.display()
+            );
+        }
+        Err(e) => {
+            anyhow::bail!(
+                "Failed to create input directory {}: {}",
+                eval_input_dir.display(),
+                e
+            );
         }
-        copy_app_dir(app_path, &args.from_framework, &eval_output_dir)?;
+    }
+    // Copy the app files into the input directory
+    copy_app_dir(app_path, &args.from_framework, &eval_input_dir)?;
 
-        let eval_validation_dir: PathBuf = eval_instance_dir.join("validation");
-        match create_dir_all(eval_instance_dir.join("validation")) {
-            Ok(_) => {
-                log::debug!(
-                    "Created validation directory: {}",
-                    eval_validation_dir.display()
-                );
-            }
-            Err(e) => {
-                anyhow::bail!(
-                    "Failed to create validation directory {}: {}",
-                    eval_validation_dir.display(),
-                    e
-                );
-            }
+    let eval_output_dir: PathBuf = eval_instance_dir.join("output");
+    match create_dir_all(&eval_output_dir) {
+        Ok(_) => {
+            log::debug!(
+                "Created output directory: {} and seeded it with the source framework",
+                eval_output_dir.display()
+            );
+        }
+        Err(e) => {
+            anyhow::bail!(
+                "Failed to create output directory {}: {}",
+                eval_output_dir.display(),
+                e
+            );
         }
+    }
+    copy_app_dir(app_path, &args.from_framework, &eval_output_dir)?;
 
-        // Update evals directory structure.
-        evals.insert(
-            eval_instance_key.clone(),
-            EvalLayout {
-                root: eval_instance_dir.clone(),
-                input: eval_input_dir.clone(),
-                output: eval_output_dir.clone(),
-                validation: eval_validation_dir.clone(),
-            },
-        );
+    let eval_validation_dir: PathBuf = eval_instance_dir.join("validation");
+    match create_dir_all(&eval_validation_dir) {
+        Ok(_) => {
+            log::debug!(
+                "Created validation directory: {}",
+                eval_validation_dir.display()
+            );
+        }
+        Err(e) => {
+            anyhow::bail!(
+                "Failed to create validation directory {}: {}",
+                eval_validation_dir.display(),
+                e
+            );
+        }
     }
-    Ok(evals)
+
+    Ok(EvalInstance::new(
+        eval_id,
+        eval_instance_dir,
+        eval_input_dir,
+        eval_output_dir,
+        eval_validation_dir,
+    ))
 }
 
-fn create_eval_metadata(eval_instance_dir: &Path, eval_id: &str) -> anyhow::Result<()> {
-    let metadata: RunMetaData = {
-        let [agent, layer, app, from_framework, to_framework]: [&str; 5] = eval_id
-            .split("__")
-            .take(5)
-            .collect::<Vec<_>>()
-            .try_into()
-            .expect("Failed to parse eval instance directory name");
-        RunMetaData {
-            eval_id: eval_id.to_owned(),
-            layer: layer.to_string(),
-            agent: agent.to_string(),
-            app: app.to_string(),
-            from_framework: from_framework.to_string(),
-            to_framework: to_framework.to_string(),
-            status: "PREPARED".to_string(),
-        }
-    };
+fn write_eval_metadata(eval_instance_dir: &Path, metadata: &RunMetaData) -> anyhow::Result<()> {
     // Generate a JSON String (that's prettified)
-    let json = serde_json::to_string_pretty(&metadata)?;
+    let json = serde_json::to_string_pretty(metadata)?;
 
     let mut file = File::create(eval_instance_dir.join("metadata.json"))?;
     file.write_all(json.as_bytes())?;
@@ -1,86 +1,10 @@
-use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::Write;
 
-use clap::{ArgAction, Args};
-use serde::Serialize;
-
-use crate::eval::{driver, prepare};
-
-#[derive(Args, Debug, Serialize)]
-pub struct EvalRunArgs {
-    #[arg(
-        long = "benchmark-dir",
-        help = "Path (directory) to the benchmark.",
-        value_name = "DIR"
-    )]
-    pub benchmark_dir: PathBuf,
-
-    #[arg(
-        long = "agent-dir",
-        help = "Path (directory) to agent implementation harness.",
-        value_name = "DIR"
-    )]
-    pub agent_dir: PathBuf,
-
-    #[arg(
-        long,
-        value_name = "LAYER",
-        action = ArgAction::Append,
-        help = "Application layer to run agent on.",
-    )]
-    pub layer: Vec<String>,
-
-    #[arg(
-        long,
-        value_name = "APP",
-        action = ArgAction::Append,
-        help = "Application to run the agent on. If layer is specified, this app must lie within that layer."
-    )]
-    pub app: Vec<String>,
-
-    #[arg(
-        long = "from-framework",
-        help = "The source framework for conversion.",
-        value_name = "FRAMEWORK"
-    )]
-    pub from_framework: String,
-
-    #[arg(
-        long = "to-framework",
-        help = "The target framework for conversion.",
-        value_name = "FRAMEWORK"
-    )]
-    pub to_framework: String,
-
-    #[arg(
-        short,
-        long = "pass-at-k",
-        default_value_t = 1,
-        help = "Value of K to run for generating an Pass@K value.",
-        value_name = "K"
-    )]
-    pub pass_at_k: u32,
-
-    #[arg(
-        long,
-        help = "Output directory where the agent runs and evaluation output are stored."
-    )]
-    pub eval_out: PathBuf,
-
-    #[arg(
-        short,
-        long = "jobs",
-        default_value_t = 1,
-        help = "Number of parallel jobs to run."
-    )]
-    pub jobs: u32,
-
-    #[arg(
-        long="prepare-only",
-        action = ArgAction::SetTrue,
-        help = "Prepare the evaluation harness to run agents. Think of this as a dry run before actually deploying the agents."
-    )]
-    pub prepare_only: bool,
-}
+use crate::cli::EvalRunArgs;
+use crate::eval::events::{EventLog, EventType};
+use crate::eval::sandbox::SandboxOptions;
+use crate::eval::{driver, prepare, score};
 
 // Create the evaluation output directory if it doesn't
 pub fn run(mut args: EvalRunArgs) -> anyhow::Result<i32> {
@@ -101,23 +25,55 @@ pub fn run(mut args: EvalRunArgs) -> anyhow::Result<i32> {
         _ => (),
     }
 
+    fs::create_dir_all(&args.eval_out)?;
+    let events = EventLog::open(&args.eval_out)?;
+
     log::info!(
         "Preparing evaluation harness at {}",
         args.eval_out.display()
     );
-    let run_layout = prepare::prepare_harness(&args)?;
+    let eval_layout = prepare::prepare_harness(&args, &events)?;
 
     if args.prepare_only {
         log::debug!("--prepare-only flag is set. Exiting after preparation.");
+        events.emit("", EventType::RunComplete, serde_json::json!({}));
         return Ok(0);
-    } else {
-        driver::dispatch_agent(
-            &args.agent_dir,
-            &args.from_framework,
-            &args.to_framework,
-            &run_layout,
-        );
     }
 
+    let timeout = args.timeout.map(std::time::Duration::from_secs);
+    let sandbox = args.sandbox.then(|| SandboxOptions {
+        allow_network: args.allow_network,
+        cpu_seconds: args.timeout.unwrap_or(3600),
+        memory_mb: args.sandbox_memory_mb,
+    });
+    let summary = driver::dispatch_agent(
+        &args.agent_dir,
+        args.jobs as usize,
+        timeout,
+        sandbox,
+        &eval_layout,
+        &events,
+    )?;
+    log::info!(
+        "Agent dispatch complete: {} succeeded, {} failed",
+        summary.succeeded.len(),
+        summary.failed.len()
+    );
+    if !summary.failed.is_empty() {
+        log::warn!("Failed instances: {}", summary.failed.join(", "));
+    }
+
+    let report = score::score_run(&eval_layout, args.pass_at_k, &events, &args.benchmark_dir)?;
+    log::info!(
+        "Pass@{} = {:.4} (aggregate across {} apps)",
+        args.pass_at_k,
+        report.aggregate_pass_at_k,
+        report.apps.len()
+    );
+    let report_path = args.eval_out.join("pass_at_k.json");
+    File::create(&report_path)?.write_all(serde_json::to_string_pretty(&report)?.as_bytes())?;
+    log::debug!("Wrote pass@k report to {}", report_path.display());
+
+    events.emit("", EventType::RunComplete, serde_json::json!({}));
     Ok(0)
 }
@@ -0,0 +1,132 @@
+use std::{fs, path::Path, process::Command};
+
+use crate::eval::types::{EvalInstance, ResourceUsage};
+
+/// Isolation and resource limits applied to a sandboxed agent run.
+#[derive(Debug, Clone, Copy)]
+pub struct SandboxOptions {
+    /// Keep network access inside the sandbox instead of dropping it.
+    pub allow_network: bool,
+    /// CPU-time rlimit, in seconds.
+    pub cpu_seconds: u64,
+    /// Memory rlimit, in megabytes.
+    pub memory_mb: u64,
+}
+
+/// Name of the file `/usr/bin/time -v` writes its resource report to, relative to the
+/// instance's `validation` dir (and thus to `/validation` inside the sandbox).
+const RESOURCE_REPORT_FILE: &str = "resource_usage.txt";
+
+/// Host directories bind-mounted read-only into the sandbox root so that, once we
+/// `chroot` into it, `/usr/bin/time`, `prlimit`, `bash`, and the shared libraries they
+/// need can still be resolved. Only directories that actually exist on the host are
+/// mounted — modern distros often fold `/bin`/`/lib*` into `/usr` via symlinks and have
+/// no separate top-level directory.
+const SYSTEM_DIRS: &[&str] = &["usr", "bin", "lib", "lib64", "lib32"];
+
+/// Emit a two-step bind mount: `mount --bind` followed by `mount -o remount,ro,bind`.
+/// A bind mount inherits read-write from its source regardless of `-o ro` passed on the
+/// initial `--bind` call; the remount is what actually makes it read-only.
+fn ro_bind_mount(host: &Path, target: &Path) -> String {
+    format!(
+        "mount --bind \"{host}\" \"{target}\"\nmount -o remount,ro,bind \"{target}\"\n",
+        host = host.display(),
+        target = target.display(),
+    )
+}
+
+/// Build the command that runs `run.sh` for one eval instance inside a fresh
+/// mount + PID (+ network, unless `allow_network`) namespace on Linux. Only the
+/// instance's own `input`/`output`/`validation` dirs and a read-only view of the agent
+/// directory and host system directories are bind-mounted in, so a misbehaving agent
+/// can't see or touch anything else on the host. CPU-time and memory rlimits are
+/// enforced via `prlimit`, and wall time / peak RSS are captured via `/usr/bin/time -v`
+/// so the caller can fold them into `metadata.json` once the run finishes.
+///
+/// This mirrors a CI runner that sets up a per-task namespace and private filesystem
+/// view before executing untrusted build steps.
+pub fn build_sandboxed_command(
+    agent_dir: &Path,
+    eval_instance: &EvalInstance,
+    opts: &SandboxOptions,
+) -> anyhow::Result<Command> {
+    let sandbox_root = eval_instance.root().join("sandbox_root");
+    for dir in ["agent", "input", "output", "validation", "proc"] {
+        fs::create_dir_all(sandbox_root.join(dir))?;
+    }
+
+    // Without a populated `/usr`, `/bin`, `/lib*`, `chroot` itself would succeed but the
+    // exec right after it would fail with "No such file or directory", since `time`,
+    // `prlimit`, and `bash` (and everything they're linked against) are resolved inside
+    // the new root, not the host's.
+    let mut system_mounts = String::new();
+    for dir in SYSTEM_DIRS {
+        let host_path = Path::new("/").join(dir);
+        if host_path.is_dir() {
+            let target = sandbox_root.join(dir);
+            fs::create_dir_all(&target)?;
+            system_mounts.push_str(&ro_bind_mount(&host_path, &target));
+        }
+    }
+
+    let script = format!(
+        r#"set -e
+{system_mounts}{agent_mount}mount --bind "{input}" "{root}/input"
+mount --bind "{output}" "{root}/output"
+mount --bind "{validation}" "{root}/validation"
+mount -t proc proc "{root}/proc"
+exec chroot "{root}" /usr/bin/time -v -o /validation/{report} \
+    prlimit --cpu={cpu} --as={mem_bytes} -- bash -lc 'cd /agent && SCARF_WORK_DIR=/output SCARF_SOURCE_FRAMEWORK="$SCARF_SOURCE_FRAMEWORK" SCARF_TARGET_FRAMEWORK="$SCARF_TARGET_FRAMEWORK" ./run.sh'
+"#,
+        system_mounts = system_mounts,
+        agent_mount = ro_bind_mount(agent_dir, &sandbox_root.join("agent")),
+        root = sandbox_root.display(),
+        input = eval_instance.input().display(),
+        output = eval_instance.output().display(),
+        validation = eval_instance.validation().display(),
+        report = RESOURCE_REPORT_FILE,
+        cpu = opts.cpu_seconds,
+        mem_bytes = opts.memory_mb * 1024 * 1024,
+    );
+
+    let mut command = Command::new("unshare");
+    command.args(["--mount", "--pid", "--fork"]);
+    if !opts.allow_network {
+        command.arg("--net");
+    }
+    command.args(["--", "bash", "-c", &script]);
+    Ok(command)
+}
+
+/// Read back the wall time / peak RSS that `/usr/bin/time -v` recorded for a sandboxed
+/// run. Returns `None` if the report is missing or couldn't be parsed (e.g. the agent
+/// never got far enough to produce one).
+pub fn read_resource_usage(eval_instance: &EvalInstance) -> Option<ResourceUsage> {
+    let report = fs::read_to_string(eval_instance.validation().join(RESOURCE_REPORT_FILE)).ok()?;
+
+    let max_rss_kb = report.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("Maximum resident set size (kbytes): ")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    })?;
+    let wall_seconds = report.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("Elapsed (wall clock) time (h:mm:ss or m:ss): ")
+            .and_then(parse_elapsed_seconds)
+    })?;
+
+    Some(ResourceUsage {
+        wall_seconds,
+        max_rss_kb,
+    })
+}
+
+/// Parse `/usr/bin/time -v`'s `m:ss` or `h:mm:ss` elapsed-time format into seconds.
+fn parse_elapsed_seconds(value: &str) -> Option<f64> {
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    let mut seconds = 0.0;
+    for part in parts {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(seconds)
+}
@@ -0,0 +1,224 @@
+use std::{
+    fs,
+    path::Path,
+    process::Command,
+};
+
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::eval::events::{EventLog, EventType};
+use crate::eval::types::{EvalInstance, EvalInstanceKey, EvalLayout, RunMetaData};
+
+/// Pass@k for a single (layer, app, from→to) tuple: `n` samples were generated, `c` of
+/// them passed validation.
+#[derive(Debug, Serialize)]
+pub struct AppScore {
+    pub eval_id: String,
+    pub n: usize,
+    pub c: usize,
+    pub pass_at_k: f64,
+}
+
+/// Per-app pass@k plus the mean across all apps in the run.
+#[derive(Debug, Serialize)]
+pub struct ScoreReport {
+    pub k: u32,
+    pub apps: Vec<AppScore>,
+    pub aggregate_pass_at_k: f64,
+}
+
+/// The unbiased pass@k estimator from the Codex paper, evaluated in the numerically
+/// stable product form to avoid overflowing the binomial coefficients directly:
+///
+/// pass@k = 1 − C(n−c, k) / C(n, k) = 1 − ∏_{i=n−c+1}^{n} (1 − k/i)
+///
+/// When fewer than `k` samples failed (`n − c < k`), every possible subset of `k` samples
+/// contains at least one passing sample, so the estimate is exactly 1.0.
+pub fn pass_at_k(n: u64, c: u64, k: u64) -> f64 {
+    if n.saturating_sub(c) < k {
+        return 1.0;
+    }
+    let product: f64 = ((n - c + 1)..=n)
+        .map(|i| 1.0 - (k as f64 / i as f64))
+        .product();
+    1.0 - product
+}
+
+/// Score a completed eval run: for every (layer, app, from→to) tuple, count how many of
+/// its samples passed and fold that into the pass@k estimator. `benchmark_dir` is the
+/// same `--benchmark-dir` the run was prepared from; it's where each sample's converted
+/// output is validated against the target framework's reference test harness.
+pub fn score_run(
+    eval_layout: &EvalLayout,
+    k: u32,
+    events: &EventLog,
+    benchmark_dir: &Path,
+) -> anyhow::Result<ScoreReport> {
+    let mut apps = Vec::with_capacity(eval_layout.len());
+
+    for (key, instances) in eval_layout {
+        let eval_id = format!(
+            "{}__{}__{}__{}__{}",
+            key.agent(),
+            key.layer(),
+            key.app(),
+            key.from_framework(),
+            key.to_framework()
+        );
+
+        let n = instances.len();
+        let mut c = 0;
+        for instance in instances {
+            if validate_sample(instance, key, benchmark_dir)? {
+                c += 1;
+            }
+        }
+
+        let app_score = AppScore {
+            eval_id: eval_id.clone(),
+            n,
+            c,
+            pass_at_k: pass_at_k(n as u64, c as u64, k as u64),
+        };
+        events.emit(
+            &eval_id,
+            EventType::Validated,
+            serde_json::json!({ "n": app_score.n, "c": app_score.c, "pass_at_k": app_score.pass_at_k }),
+        );
+        apps.push(app_score);
+    }
+
+    let aggregate_pass_at_k = if apps.is_empty() {
+        0.0
+    } else {
+        apps.iter().map(|app| app.pass_at_k).sum::<f64>() / apps.len() as f64
+    };
+
+    Ok(ScoreReport {
+        k,
+        apps,
+        aggregate_pass_at_k,
+    })
+}
+
+/// Harness files copied from the target framework's reference app into a sample's
+/// `output` dir before validation. `prepare::copy_app_dir` withholds these from the agent
+/// so it can't just copy the expected test harness verbatim; it also withholds
+/// `Dockerfile`/`.dockerignore`, which are not copied back here.
+const HARNESS_FILES: &[&str] = &["Makefile", "smoke.py", "smoke"];
+
+/// Status written back to a sample's `metadata.json` once `score_run` has validated it,
+/// replacing whatever agent-exit status `driver.rs` had recorded there. This is the
+/// status `eval compare` gates regressions on, since the agent exiting 0 only means its
+/// process ran; it says nothing about whether the code it produced actually converts.
+pub(crate) const VALIDATION_PASSED: &str = "VALIDATION PASSED";
+const VALIDATION_FAILED: &str = "VALIDATION FAILED";
+
+/// A sample passed validation if its agent process exited cleanly *and* the code it
+/// produced actually converts: we drop the target framework's `Makefile`/smoke test into
+/// the sample's `output` dir and require `make test` to succeed there, the same way
+/// `bench test` validates a benchmark application directly. The verdict is persisted
+/// back into the sample's `metadata.json` so later tooling (e.g. `eval compare`) can gate
+/// on actual conversion correctness instead of the agent's raw exit status.
+fn validate_sample(
+    eval_instance: &EvalInstance,
+    key: &EvalInstanceKey,
+    benchmark_dir: &Path,
+) -> anyhow::Result<bool> {
+    let metadata_path = eval_instance.root().join("metadata.json");
+    let metadata_file = fs::read_to_string(&metadata_path)?;
+    let mut run_metadata: RunMetaData = serde_json::from_str(&metadata_file)?;
+
+    let passed = sample_converts(eval_instance, key, benchmark_dir, &run_metadata)?;
+
+    run_metadata.set_status(if passed { VALIDATION_PASSED } else { VALIDATION_FAILED }.to_string());
+    fs::write(&metadata_path, serde_json::to_string_pretty(&run_metadata)?).with_context(|| {
+        format!(
+            "Failed to write validation status to {}",
+            metadata_path.display()
+        )
+    })?;
+
+    Ok(passed)
+}
+
+/// Run the actual `make test` check for one sample; `validate_sample` is the one that
+/// persists the verdict. Returns `false` without attempting validation if the agent
+/// itself never finished, since there's nothing in `output` worth testing.
+fn sample_converts(
+    eval_instance: &EvalInstance,
+    key: &EvalInstanceKey,
+    benchmark_dir: &Path,
+    run_metadata: &RunMetaData,
+) -> anyhow::Result<bool> {
+    if run_metadata.status() != "AGENT EXECUTION COMPLETE" {
+        return Ok(false);
+    }
+
+    let harness_dir = benchmark_dir
+        .join(key.layer())
+        .join(key.app())
+        .join(key.to_framework());
+    for file in HARNESS_FILES {
+        let src = harness_dir.join(file);
+        if src.exists() {
+            fs::copy(&src, eval_instance.output().join(file)).with_context(|| {
+                format!(
+                    "Failed to copy validation harness file {} into {}",
+                    src.display(),
+                    eval_instance.output().display()
+                )
+            })?;
+        }
+    }
+
+    if !eval_instance.output().join("Makefile").exists() {
+        log::warn!(
+            "No Makefile found for {} at {}; cannot validate conversion output",
+            eval_instance.eval_id(),
+            harness_dir.display()
+        );
+        return Ok(false);
+    }
+
+    let status = Command::new("make")
+        .arg("test")
+        .current_dir(eval_instance.output())
+        .status()
+        .with_context(|| {
+            format!(
+                "Failed to run 'make test' in {}",
+                eval_instance.output().display()
+            )
+        })?;
+    Ok(status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pass_at_k_is_one_when_too_few_failures_to_miss_a_pass() {
+        // n=5 samples, c=4 passed => only 1 failed; with k=2 every 2-subset has a pass.
+        assert_eq!(pass_at_k(5, 4, 2), 1.0);
+    }
+
+    #[test]
+    fn pass_at_k_is_one_when_all_samples_passed() {
+        assert_eq!(pass_at_k(10, 10, 3), 1.0);
+    }
+
+    #[test]
+    fn pass_at_k_is_zero_when_nothing_passed() {
+        assert_eq!(pass_at_k(10, 0, 3), 0.0);
+    }
+
+    #[test]
+    fn pass_at_k_matches_raw_pass_rate_for_k_one() {
+        let (n, c) = (8, 3);
+        let estimate = pass_at_k(n, c, 1);
+        assert!((estimate - (c as f64 / n as f64)).abs() < 1e-9);
+    }
+}
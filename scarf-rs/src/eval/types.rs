@@ -0,0 +1,187 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Identifies one (agent, layer, app, from→to) conversion slot in an eval run.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EvalInstanceKey {
+    agent: String,
+    layer: String,
+    app: String,
+    from_framework: String,
+    to_framework: String,
+}
+
+impl EvalInstanceKey {
+    pub fn new(
+        agent: impl Into<String>,
+        layer: impl Into<String>,
+        app: impl Into<String>,
+        from_framework: impl Into<String>,
+        to_framework: impl Into<String>,
+    ) -> Self {
+        Self {
+            agent: agent.into(),
+            layer: layer.into(),
+            app: app.into(),
+            from_framework: from_framework.into(),
+            to_framework: to_framework.into(),
+        }
+    }
+
+    pub fn agent(&self) -> &str {
+        &self.agent
+    }
+
+    pub fn layer(&self) -> &str {
+        &self.layer
+    }
+
+    pub fn app(&self) -> &str {
+        &self.app
+    }
+
+    pub fn from_framework(&self) -> &str {
+        &self.from_framework
+    }
+
+    pub fn to_framework(&self) -> &str {
+        &self.to_framework
+    }
+}
+
+/// One sample directory (input/output/validation) prepared for a single eval instance.
+#[derive(Debug, Clone)]
+pub struct EvalInstance {
+    eval_id: String,
+    root: PathBuf,
+    input: PathBuf,
+    output: PathBuf,
+    validation: PathBuf,
+}
+
+impl EvalInstance {
+    pub fn new(
+        eval_id: impl Into<String>,
+        root: PathBuf,
+        input: PathBuf,
+        output: PathBuf,
+        validation: PathBuf,
+    ) -> Self {
+        Self {
+            eval_id: eval_id.into(),
+            root,
+            input,
+            output,
+            validation,
+        }
+    }
+
+    pub fn eval_id(&self) -> &str {
+        &self.eval_id
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn input(&self) -> &Path {
+        &self.input
+    }
+
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+
+    pub fn validation(&self) -> &Path {
+        &self.validation
+    }
+}
+
+/// All eval instances prepared for a run, keyed by the (agent, layer, app, from→to) tuple
+/// they belong to. Each key can map to several samples once Pass@K sampling is in play.
+pub type EvalLayout = HashMap<EvalInstanceKey, Vec<EvalInstance>>;
+
+/// Wall time and peak memory observed for a sandboxed agent run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    pub wall_seconds: f64,
+    pub max_rss_kb: u64,
+}
+
+/// Metadata persisted as `metadata.json` alongside each eval instance, tracking its status
+/// end to end (prepared, dispatched, scored).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMetaData {
+    eval_id: String,
+    agent: String,
+    layer: String,
+    app: String,
+    from_framework: String,
+    to_framework: String,
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resource_usage: Option<ResourceUsage>,
+}
+
+impl RunMetaData {
+    pub fn new(
+        eval_id: impl Into<String>,
+        agent: impl Into<String>,
+        layer: impl Into<String>,
+        app: impl Into<String>,
+        from_framework: impl Into<String>,
+        to_framework: impl Into<String>,
+        status: impl Into<String>,
+    ) -> Self {
+        Self {
+            eval_id: eval_id.into(),
+            agent: agent.into(),
+            layer: layer.into(),
+            app: app.into(),
+            from_framework: from_framework.into(),
+            to_framework: to_framework.into(),
+            status: status.into(),
+            resource_usage: None,
+        }
+    }
+
+    pub fn eval_id(&self) -> &str {
+        &self.eval_id
+    }
+
+    pub fn agent(&self) -> &str {
+        &self.agent
+    }
+
+    pub fn layer(&self) -> &str {
+        &self.layer
+    }
+
+    pub fn app(&self) -> &str {
+        &self.app
+    }
+
+    pub fn source_framework(&self) -> &str {
+        &self.from_framework
+    }
+
+    pub fn target_framework(&self) -> &str {
+        &self.to_framework
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn set_status(&mut self, status: String) {
+        self.status = status;
+    }
+
+    pub fn set_resource_usage(&mut self, usage: ResourceUsage) {
+        self.resource_usage = Some(usage);
+    }
+}
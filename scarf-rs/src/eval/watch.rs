@@ -0,0 +1,89 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    thread,
+    time::Duration,
+};
+
+use comfy_table::Table;
+
+use crate::cli::EvalWatchArgs;
+use crate::eval::events::{Event, EventType};
+
+/// How long to wait before re-checking `events.jsonl` for new lines once we've caught up.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Follow `<eval_out>/events.jsonl` like `tail -f`, rendering a live-updating summary of
+/// per-instance status until a terminal `run_complete` event is seen.
+pub fn run(args: EvalWatchArgs) -> anyhow::Result<i32> {
+    let events_path = args.eval_out.join("events.jsonl");
+    let mut file = File::open(&events_path)?;
+    let mut reader = BufReader::new(file.try_clone()?);
+    let mut statuses: BTreeMap<String, String> = BTreeMap::new();
+
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader.read_line(&mut line)?;
+
+        if bytes_read == 0 {
+            // Caught up with the writer; remember where we are and wait for more to
+            // be appended, re-seeking past EOF each time.
+            let pos = reader.stream_position()?;
+            thread::sleep(POLL_INTERVAL);
+            file.seek(SeekFrom::Start(pos))?;
+            reader = BufReader::new(file.try_clone()?);
+            continue;
+        }
+
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<Event>(trimmed) {
+            Ok(event) => {
+                if event.event_type == EventType::RunComplete {
+                    render(&statuses);
+                    log::info!("Eval run complete.");
+                    return Ok(0);
+                }
+                statuses.insert(event.eval_id.clone(), describe(&event));
+                render(&statuses);
+            }
+            Err(e) => {
+                log::warn!("Skipping malformed event line {:?}: {}", trimmed, e);
+            }
+        }
+    }
+}
+
+fn describe(event: &Event) -> String {
+    match event.event_type {
+        EventType::Prepared => "Prepared".to_string(),
+        EventType::AgentStarted => "Running".to_string(),
+        EventType::AgentFinished => {
+            if event.payload.get("succeeded").and_then(|v| v.as_bool()) == Some(true) {
+                "Agent complete".to_string()
+            } else {
+                "Agent failed".to_string()
+            }
+        }
+        EventType::Validated => {
+            let n = event.payload.get("n").and_then(|v| v.as_u64()).unwrap_or(0);
+            let c = event.payload.get("c").and_then(|v| v.as_u64()).unwrap_or(0);
+            format!("Validated ({c}/{n} passed)")
+        }
+        EventType::RunComplete => "Run complete".to_string(),
+    }
+}
+
+fn render(statuses: &BTreeMap<String, String>) {
+    print!("\x1B[2J\x1B[H");
+    let mut table = Table::new();
+    table.set_header(vec!["Eval Instance", "Status"]);
+    for (eval_id, status) in statuses {
+        table.add_row(vec![eval_id.as_str(), status.as_str()]);
+    }
+    println!("{}", table);
+}
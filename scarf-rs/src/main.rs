@@ -3,12 +3,15 @@ use clap::Parser;
 
 mod cli;
 mod bench;
+mod eval;
+mod utils;
 
 fn main() -> Result<()> {
     let cli = cli::Cli::parse();
 
     let code = match cli.command {
-        cli::Commands::Bench(cmd) => bench::run(cmd)?
+        cli::Commands::Bench(cmd) => bench::run(cmd)?,
+        cli::Commands::Eval(cmd) => eval::run(cmd)?,
     };
-std::process::exit(code);
+    std::process::exit(code);
 }